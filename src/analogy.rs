@@ -0,0 +1,171 @@
+pub mod analogy
+{
+    use std::fmt;
+    use crate::lib::{Phonet, Phonet::*, Feature, FeatureValue, features_of, feature_value, with_feature_value};
+    use crate::plausibility::plausibility::{plausibility, Verdict, Plausibility::ChartImpossible};
+    use crate::grapheme::international_phonetic_alphabet::international_phonetic_alphabet::{construct_transcription, can_transcribe};
+
+    /// One feature that changes between the first two
+    /// terms of an analogy.
+    #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+    pub struct FeatureChange
+    {
+        pub from: FeatureValue,
+        pub to: FeatureValue
+    }
+
+    impl fmt::Display for FeatureChange
+    {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            write!(f, "{}: {} → {}", self.from.feature(), self.from, self.to)
+        }
+    }
+
+    /// The answer to "a : b :: c : ?".
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub struct AnalogySolution
+    {
+        pub answer: Phonet,
+        pub transcription: Option<String>,  // None if the answer has no IPA symbol.
+        pub changes: Vec<FeatureChange>
+    }
+
+    /// Why an analogy has no consistent answer.
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub enum AnalogyError
+    {
+        // The first pair are a consonant and a vowel,
+        // so there are no shared features to compare.
+        FirstPairDiffersInKind,
+
+        // The third term is not the same kind (consonant or vowel)
+        // as the first pair.
+        ThirdDiffersInKind,
+
+        // The third term does not start from the same value
+        // as the first term, for a feature that changes.
+        Mismatch {feature: Feature, expected: FeatureValue, found: FeatureValue},
+
+        // The answer would be a sound that is judged impossible.
+        ImpossibleAnswer {answer: Phonet, verdict: Verdict}
+    }
+
+    impl fmt::Display for AnalogyError
+    {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            match self
+            {
+                AnalogyError::FirstPairDiffersInKind =>
+                  write!(f, "the first pair are a consonant and a vowel, so they have no features to compare"),
+                AnalogyError::ThirdDiffersInKind =>
+                  write!(f, "the third sound is not the same kind (consonant or vowel) as the first pair"),
+                AnalogyError::Mismatch {feature, expected, found} =>
+                  write!(f, "{} changes from {} in the first pair, but the third sound has {}", feature, expected, found),
+                AnalogyError::ImpossibleAnswer {answer, verdict} =>
+                  write!(f, "the answer {:?} is {}", answer, verdict),
+            }
+        }
+    }
+
+
+    /// Lists the features that differ between two phonets
+    /// of the same kind, in the order of features_of.
+    /// Returns None if one is a consonant and the other a vowel.
+    pub fn feature_changes(a: Phonet, b: Phonet) -> Option<Vec<FeatureChange>>
+    {
+        if !same_kind(a, b)
+        {
+            return None;
+        }
+        let changes = features_of(a).iter()
+                        .filter_map(|feature| match (feature_value(a, *feature), feature_value(b, *feature))
+                        {
+                            (Some(from), Some(to)) if from != to => Some(FeatureChange {from, to}),
+                            _ => None,
+                        })
+                        .collect();
+        Some(changes)
+    }
+
+    /// Solves "a : b :: c : ?" by finding the features
+    /// that change from a to b, and changing them the same way in c.
+    pub fn solve_analogy(a: Phonet, b: Phonet, c: Phonet) -> Result<AnalogySolution, AnalogyError>
+    {
+        let changes = feature_changes(a, b).ok_or(AnalogyError::FirstPairDiffersInKind)?;
+        if !same_kind(a, c)
+        {
+            return Err(AnalogyError::ThirdDiffersInKind);
+        }
+
+        let mut answer = c;
+        for change in changes.iter()
+        {
+            let feature = change.from.feature();
+            // Both are the same kind as a, so they have every feature a has.
+            let found = feature_value(c, feature).unwrap();
+            if found != change.from
+            {
+                return Err(AnalogyError::Mismatch {feature, expected: change.from, found});
+            }
+            answer = with_feature_value(answer, change.to).unwrap();
+        }
+
+        let verdict = plausibility(answer);
+        if verdict.plausibility <= ChartImpossible
+        {
+            return Err(AnalogyError::ImpossibleAnswer {answer, verdict});
+        }
+
+        let transcription = if can_transcribe(answer) { Some(construct_transcription(answer)) } else { None };
+        Ok(AnalogySolution {answer, transcription, changes})
+    }
+
+    fn same_kind(a: Phonet, b: Phonet) -> bool
+    {
+        matches!((a, b), (Consonant {..}, Consonant {..}) | (Vowel {..}, Vowel {..}))
+    }
+
+
+    #[cfg(test)]
+    mod analogy_tests
+    {
+        use super::*;
+        use crate::grapheme::international_phonetic_alphabet::international_phonetic_alphabet::analyze_transcription;
+        use crate::plausibility::plausibility::Plausibility;
+
+        fn solve(a: &str, b: &str, c: &str) -> Result<AnalogySolution, AnalogyError>
+        {
+            solve_analogy(analyze_transcription(String::from(a)),
+                          analyze_transcription(String::from(b)),
+                          analyze_transcription(String::from(c)))
+        }
+
+        #[test]
+        fn solve_analogy_test_voicing()
+        {
+            assert_eq!(solve("p", "b", "t").unwrap().transcription, Some(String::from("d")));
+        }
+
+        #[test]
+        fn solve_analogy_test_vowels()
+        {
+            assert_eq!(solve("i", "u", "e").unwrap().transcription, Some(String::from("o")));
+        }
+
+        #[test]
+        fn solve_analogy_test_mismatch()
+        {
+            assert!(matches!(solve("p", "b", "d"), Err(AnalogyError::Mismatch {..})));
+        }
+
+        #[test]
+        fn solve_analogy_test_impossible_answer()
+        {
+            // p : m :: ʔ : a glottal nasal, which cannot be articulated.
+            let error = solve("p", "m", "ʔ").unwrap_err();
+            assert!(matches!(error, AnalogyError::ImpossibleAnswer {verdict: Verdict {plausibility: Plausibility::Impossible, ..}, ..}));
+        }
+    }
+}
@@ -3,6 +3,13 @@ pub mod english_us_text
     pub(crate) const USER_INPUT_VIEW_ENGLISH_PHONEME_INVENTORY: &str = "1";
     pub(crate) const USER_INPUT_MAKE_A_PHONEME_VOICED: &str = "2";
     pub(crate) const USER_INPUT_MAKE_A_PHONEME_UNVOICED: &str = "3";
+    pub(crate) const USER_INPUT_COMPARE_TWO_PHONEMES: &str = "4";
+    pub(crate) const USER_INPUT_APPLY_TRANSFORMATIONS: &str = "5";
+    pub(crate) const USER_INPUT_ASPIRATE: &str = "6";
+    pub(crate) const USER_INPUT_DEASPIRATE: &str = "7";
+    pub(crate) const USER_INPUT_PREASPIRATE: &str = "8";
+    pub(crate) const USER_INPUT_GEMINATE: &str = "9";
+    pub(crate) const USER_INPUT_DEGEMINATE: &str = "10";
 
     pub(crate) const THE_USER_SELECTED: &str = "The user selected:";
     pub(crate) const USER_SELECTION_NOT_HANDLED: &str = "User selection not handled";
@@ -13,11 +20,27 @@ pub mod english_us_text
     pub(crate) const PHONEME_TO_DEVOICE_MESSAGE: &str = "Enter the phoneme you would like to voice:";
     pub(crate) const FAILED_TO_READ_USER_INPUT: &str = "Failed to read user input.";
     pub(crate) const PHONEME_TO_VOICE_MESSAGE: &str = "Enter the phoneme you would like to devoice:";
+    pub(crate) const FIRST_PHONEME_TO_COMPARE_MESSAGE: &str = "Enter the first phoneme to compare:";
+    pub(crate) const SECOND_PHONEME_TO_COMPARE_MESSAGE: &str = "Enter the second phoneme to compare:";
+    pub(crate) const TRANSFORMATIONS_TO_APPLY_MESSAGE: &str = "Enter the transformations to apply, separated by \"then\" (voice, devoice, spirantize, retract, advance, aspirate, deaspirate):";
+    pub(crate) const TRANSCRIPTION_TO_TRANSFORM_MESSAGE: &str = "Enter the transcription to transform:";
+    pub(crate) const TRANSCRIPTION_TO_ASPIRATE_MESSAGE: &str = "Enter the transcription you would like to aspirate:";
+    pub(crate) const TRANSCRIPTION_TO_DEASPIRATE_MESSAGE: &str = "Enter the transcription you would like to deaspirate:";
+    pub(crate) const TRANSCRIPTION_TO_PREASPIRATE_MESSAGE: &str = "Enter the transcription you would like to preaspirate:";
+    pub(crate) const TRANSCRIPTION_TO_GEMINATE_MESSAGE: &str = "Enter the transcription you would like to geminate (or lengthen):";
+    pub(crate) const TRANSCRIPTION_TO_DEGEMINATE_MESSAGE: &str = "Enter the transcription you would like to degeminate (or shorten):";
     pub(crate) const MENU: &str = "What do you want to accomplish?
 
 1) view the English phoneme inventory (as IPA graphemes).
 2) make a phoneme voiced.
 3) make a phoneme unvoiced.
+4) compare two phonemes feature by feature.
+5) apply transformations (for example devoice then spirantize) to a transcription.
+6) aspirate the consonants of a transcription.
+7) deaspirate the consonants of a transcription.
+8) preaspirate the consonants of a transcription.
+9) geminate (or lengthen) the segments of a transcription.
+10) degeminate (or shorten) the segments of a transcription.
 
 Enter the number representing your selection below, after the prompt, and press enter/return.\n\n\n";
 
@@ -0,0 +1,135 @@
+pub mod feature_diff
+{
+    use std::fmt;
+    use crate::lib::{Phonet, Phonet::*, Feature, FeatureValue, feature_value, ALL_FEATURES};
+
+    /// How one feature differs between two phonets.
+    /// A value is None when that phonet does not have the feature,
+    /// for example the place of articulation of a vowel.
+    #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+    pub struct FeatureDifference
+    {
+        pub feature: Feature,
+        pub before: Option<FeatureValue>,
+        pub after: Option<FeatureValue>
+    }
+
+    impl fmt::Display for FeatureDifference
+    {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            write!(f, "{}: {} → {}", self.feature, describe_value(self.before), describe_value(self.after))
+        }
+    }
+
+    fn describe_value(value: Option<FeatureValue>) -> String
+    {
+        match value
+        {
+            Some(x) => x.to_string(),
+            None    => String::from("(none)"),
+        }
+    }
+
+    /// Every way in which one phonet differs from another.
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub struct PhonetDiff
+    {
+        pub before: Phonet,
+        pub after: Phonet,
+        pub differences: Vec<FeatureDifference>
+    }
+
+    impl PhonetDiff
+    {
+        /// Whether the two phonets are the same.
+        pub fn is_empty(&self) -> bool
+        {
+            self.differences.is_empty()
+        }
+
+        /// Whether one phonet is a consonant and the other a vowel.
+        pub fn kind_changed(&self) -> bool
+        {
+            kind_name(self.before) != kind_name(self.after)
+        }
+    }
+
+    impl fmt::Display for PhonetDiff
+    {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            write!(f, "{}", render_diff(self))
+        }
+    }
+
+    fn kind_name(phonet: Phonet) -> &'static str
+    {
+        match phonet
+        {
+            Consonant {..} => "Consonant",
+            Vowel {..}     => "Vowel",
+        }
+    }
+
+
+    /// Compares two phonets feature by feature. Consonants and
+    /// vowels can be compared too, then the features only one
+    /// of them has are reported as added or removed.
+    pub fn diff_phonets(before: Phonet, after: Phonet) -> PhonetDiff
+    {
+        let differences = ALL_FEATURES.iter()
+                            .map(|feature| FeatureDifference { feature: *feature
+                                                             , before: feature_value(before, *feature)
+                                                             , after: feature_value(after, *feature)
+                                                             })
+                            .filter(|d| d.before != d.after)
+                            .collect();
+        PhonetDiff {before, after, differences}
+    }
+
+    /// Writes a diff on one line, for example
+    /// "vocal_folds: Voiceless → Voiced; place: Alveolar → Dental".
+    pub fn render_diff(diff: &PhonetDiff) -> String
+    {
+        if diff.is_empty()
+        {
+            return String::from("no differences");
+        }
+
+        let mut parts: Vec<String> = Vec::new();
+        if diff.kind_changed()
+        {
+            parts.push(format!("kind: {} → {}", kind_name(diff.before), kind_name(diff.after)));
+        }
+        for difference in diff.differences.iter()
+        {
+            parts.push(difference.to_string());
+        }
+        parts.join("; ")
+    }
+
+
+    #[cfg(test)]
+    mod feature_diff_tests
+    {
+        use super::*;
+        use crate::grapheme::international_phonetic_alphabet::international_phonetic_alphabet::analyze_transcription;
+        use crate::lib::{VocalFolds::*, Place::*, Manner::*, Airstream::*};
+
+        #[test]
+        fn render_diff_test_voicing_and_place()
+        {
+            let t = analyze_transcription(String::from("t"));
+            let dental_d = Consonant {vocal_folds: Voiced, place: Dental, manner: Plosive, airstream: PulmonicEgressive};
+            assert_eq!(render_diff(&diff_phonets(t, dental_d)), "vocal_folds: Voiceless → Voiced; place: Alveolar → Dental");
+        }
+
+        #[test]
+        fn render_diff_test_consonant_and_vowel()
+        {
+            let diff = diff_phonets(analyze_transcription(String::from("j")), analyze_transcription(String::from("i")));
+            assert!(render_diff(&diff).starts_with("kind: Consonant → Vowel; place: Palatal → (none)"));
+        }
+    }
+}
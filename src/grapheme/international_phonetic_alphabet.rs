@@ -1,6 +1,6 @@
 pub mod international_phonetic_alphabet
 {
-    use phon_mod::lib::
+    use crate::lib::
         {Phonet, Phonet::*,
          VocalFolds, VocalFolds::*,
          Place, Place::*,
@@ -10,6 +10,7 @@ pub mod international_phonetic_alphabet
          Backness::*,
          Rounding::*,
          voiced_phonet,
+         deaspirated_phonet,
          devoiced_phonet,
          spirantized_phonet};
 
@@ -31,41 +32,37 @@ pub mod international_phonetic_alphabet
 
     fn analyze_manner_transcription(x: char) -> (Manner, usize)
     {
-        if CONSONANTS_PULMONIC_TABLE[0].iter().any(|&elem| elem == x) == true
+        if CONSONANTS_PULMONIC_TABLE[0].contains(&x)
         {
-            return (Plosive, 0);
+            (Plosive, 0)
         }
-        else if CONSONANTS_PULMONIC_TABLE[1].iter().any(|&elem| elem == x) == true
+        else if CONSONANTS_PULMONIC_TABLE[1].contains(&x)
         {
-            return (Nasal, 1);
+            (Nasal, 1)
         }
-        else if CONSONANTS_PULMONIC_TABLE[2].iter().any(|&elem| elem == x) == true
+        else if CONSONANTS_PULMONIC_TABLE[2].contains(&x)
         {
-            return (Trill, 2);
+            (Trill, 2)
         }
-        else if CONSONANTS_PULMONIC_TABLE[3].iter().any(|&elem| elem == x) == true
+        else if CONSONANTS_PULMONIC_TABLE[3].contains(&x)
         {
-            return (TapOrFlap, 3);
+            (TapOrFlap, 3)
         }
-        else if CONSONANTS_PULMONIC_TABLE[4].iter().any(|&elem| elem == x) == true
+        else if CONSONANTS_PULMONIC_TABLE[4].contains(&x)
         {
-            return (Fricative, 4);
+            (Fricative, 4)
         }
-        else if CONSONANTS_PULMONIC_TABLE[5].iter().any(|&elem| elem == x) == true
+        else if CONSONANTS_PULMONIC_TABLE[5].contains(&x)
         {
-            return (LateralFricative, 5);
+            (LateralFricative, 5)
         }
-        else if CONSONANTS_PULMONIC_TABLE[6].iter().any(|&elem| elem == x) == true
+        else if CONSONANTS_PULMONIC_TABLE[6].contains(&x)
         {
-            return (Approximant, 6);
-        }
-        else if CONSONANTS_PULMONIC_TABLE[7].iter().any(|&elem| elem == x) == true
-        {
-            return (LateralApproximant, 7);
+            (Approximant, 6)
         }
         else
         {
-            return (LateralApproximant, 7); // Not right, but will have to work for now. // TODO: Fix this.
+            (LateralApproximant, 7) // Also covers row 7 itself. Not right, but will have to work for now. // TODO: Fix this.
         }
     }
     
@@ -79,7 +76,11 @@ pub mod international_phonetic_alphabet
     fn analyze_transcriptionv2(x: char) -> Phonet
     {
         let (manner1, row_index) = analyze_manner_transcription(x);
-        let col_index = CONSONANTS_PULMONIC_TABLE[row_index].iter().position(|&elem| elem == x).unwrap();
+        let col_index = match CONSONANTS_PULMONIC_TABLE[row_index].iter().position(|&elem| elem == x)
+        {
+            Some(col_index) => col_index,
+            None => return Consonant {vocal_folds: UnmarkedVocalFolds, place: UnmarkedPlace, manner: UnmarkedManner, airstream: UnmarkedAirstream}, // Not recognized.
+        };
         let voicing   = col_index_to_voicing(col_index);
         let place1    = analyze_place_transcription(col_index);
         Consonant {vocal_folds: voicing, place: place1, manner: manner1, airstream: PulmonicEgressive}
@@ -88,13 +89,11 @@ pub mod international_phonetic_alphabet
 
     fn col_index_to_voicing(col_index: usize) -> VocalFolds
     {
-        if col_index % 2 == 0
-        {
-            Voiceless
-        }
-        else
+        // Voiceless symbols are in the even columns of the table.
+        match col_index % 2
         {
-            Voiced
+            0 => Voiceless,
+            _ => Voiced,
         }
     }
     
@@ -111,22 +110,24 @@ pub mod international_phonetic_alphabet
         }
     }
     
-    fn manner_to_row_index(manner: Manner) -> usize
+    // Returns None for manners that have no row in the table.
+    fn manner_to_row_index(manner: Manner) -> Option<usize>
     {
         let row_names: [Manner; 8] = [Plosive, Nasal, Trill, TapOrFlap, Fricative, LateralFricative, Approximant, LateralApproximant];
-        row_names.iter().position(|&elem| elem == manner).unwrap()
+        row_names.iter().position(|&elem| elem == manner)
     }
     
 
-    fn place_to_half_col_index(place: Place) -> usize
+    // Returns None for places that have no column in the table.
+    fn place_to_half_col_index(place: Place) -> Option<usize>
     {
       let col_names: [Place; 11] = [Bilabial, LabioDental, Dental, Alveolar, PostAlveolar, Retroflex, Palatal, Velar, Uvular, Pharyngeal, Glottal];
-      col_names.iter().position(|&elem| elem == place).unwrap()
+      col_names.iter().position(|&elem| elem == place)
     }
     
-    fn voicing_and_place_to_col_index(voicing: VocalFolds, place: Place) -> usize
+    fn voicing_and_place_to_col_index(voicing: VocalFolds, place: Place) -> Option<usize>
     {
-      (2 * place_to_half_col_index(place)) + voicing_to_col_index_offset(voicing)
+      place_to_half_col_index(place).map(|half| (2 * half) + voicing_to_col_index_offset(voicing))
     }
 
 
@@ -140,6 +141,12 @@ pub mod international_phonetic_alphabet
             // Affricates
             "t͡ʃ" => Consonant {vocal_folds: Voiceless, place: PostAlveolar, manner: Affricate, airstream: PulmonicEgressive},
             "d͡ʒ" => Consonant {vocal_folds: Voiced   , place: PostAlveolar, manner: Affricate, airstream: PulmonicEgressive},
+            "p͡ɸ" => Consonant {vocal_folds: Voiceless, place: Bilabial    , manner: Affricate, airstream: PulmonicEgressive},
+            "t͜s" => Consonant {vocal_folds: Voiceless, place: Alveolar    , manner: Affricate, airstream: PulmonicEgressive},
+            "t͡s" => Consonant {vocal_folds: Voiceless, place: Alveolar    , manner: Affricate, airstream: PulmonicEgressive},
+            "d͡z" => Consonant {vocal_folds: Voiced   , place: Alveolar    , manner: Affricate, airstream: PulmonicEgressive},
+            "k͡x" => Consonant {vocal_folds: Voiceless, place: Velar       , manner: Affricate, airstream: PulmonicEgressive},
+            "q͡χ" => Consonant {vocal_folds: Voiceless, place: Uvular      , manner: Affricate, airstream: PulmonicEgressive},
             // We should probably enforce use of the tie-bar underneath, otherwise
             // it would not be deterministic to determine whether two graphemes here
             // represent affricates or a plosive followed by a fricative.
@@ -214,14 +221,20 @@ pub mod international_phonetic_alphabet
             "ɒ"  => Vowel {height: Open     , backness: Back   , rounding: Rounded         , vocal_folds:  Voiced},
 
 
-            x if x.len() == 1  => analyze_transcriptionv2(x[0..1].to_string().chars().next().unwrap()),
+            x if x.chars().count() == 1 => analyze_transcriptionv2(x.chars().next().unwrap()),
+            "" => Consonant {vocal_folds: UnmarkedVocalFolds, place: UnmarkedPlace, manner: UnmarkedManner, airstream: UnmarkedAirstream}, // Not recognized.
             x =>
             {
                 // Handle Diacritics:
+                // (Compare by character, not by byte, because
+                // most IPA symbols take more than one byte.)
+                let mut characters = x.chars();
+                let base = characters.next().unwrap().to_string();
+                let diacritic: String = characters.collect();
 
-                if x[1..2] == "̥".to_string()
+                if diacritic == "̥"
                 {
-                    let full_grapheme = analyze_transcription(x[0..1].to_string());
+                    let full_grapheme = analyze_transcription(base);
                     
                     match full_grapheme
                     {
@@ -230,9 +243,9 @@ pub mod international_phonetic_alphabet
                     }
 
                 }
-                else if x[1..2] == "̬".to_string()
+                else if diacritic == "̬"
                 {
-                    let full_grapheme = analyze_transcription(x[0..1].to_string());
+                    let full_grapheme = analyze_transcription(base);
                     
                     match full_grapheme
                     {
@@ -241,9 +254,9 @@ pub mod international_phonetic_alphabet
                     }
 
                 }
-                else if x[1..2] == "ʰ".to_string()
+                else if diacritic == "ʰ"
                 {
-                    let full_grapheme = analyze_transcription(String::from(x[0..1].to_string()));
+                    let full_grapheme = analyze_transcription(base);
                     match full_grapheme
                     {
                             Consonant {vocal_folds: Voiced   , place: place1, manner: manner1, airstream: airstream1 } => Consonant {vocal_folds: VoicedAspirated   , place: place1, manner: manner1, airstream: airstream1 },
@@ -256,6 +269,15 @@ pub mod international_phonetic_alphabet
 
                     }
                 }
+                else if diacritic == "ʱ"
+                {
+                    // Breathy voiced aspiration, only on a voiced consonant.
+                    match analyze_transcription(base)
+                    {
+                            Consonant {vocal_folds: Voiced, place: place1, manner: manner1, airstream: airstream1 } => Consonant {vocal_folds: VoicedAspirated, place: place1, manner: manner1, airstream: airstream1 },
+                            _ => Consonant {vocal_folds: UnmarkedVocalFolds, place: UnmarkedPlace, manner: UnmarkedManner, airstream: UnmarkedAirstream}
+                    }
+                }
                 else
                 {
                     Consonant {vocal_folds: UnmarkedVocalFolds, place: UnmarkedPlace, manner: UnmarkedManner, airstream: UnmarkedAirstream}
@@ -272,27 +294,17 @@ pub mod international_phonetic_alphabet
         {
             Consonant{vocal_folds: voicing1, place: place1, manner: manner1, airstream: _} =>
             {
-                let row_index = manner_to_row_index(manner1);
-                let col_index = voicing_and_place_to_col_index(voicing1, place1);
-                (CONSONANTS_PULMONIC_TABLE[row_index][col_index]).to_string()
+                match (manner_to_row_index(manner1), voicing_and_place_to_col_index(voicing1, place1))
+                {
+                    (Some(row_index), Some(col_index)) => (CONSONANTS_PULMONIC_TABLE[row_index][col_index]).to_string(),
+                    _ => String::from(" "), // Same as an empty cell of the table.
+                }
             },
             _ => String::from(""),
         }
     }
 
 
-    fn deaspirate(phone_description: Phonet) -> Phonet
-    {
-        match phone_description
-        {
-            Consonant {vocal_folds: VoicedAspirated   , place: place1, manner: manner1, airstream: airstream1} => Consonant {vocal_folds: Voiced   , place: place1, manner: manner1, airstream: airstream1},
-            Consonant {vocal_folds: VoicelessAspirated, place: place1, manner: manner1, airstream: airstream1} => Consonant {vocal_folds: Voiceless, place: place1, manner: manner1, airstream: airstream1},
-            x => x,
-        }
-    }
-
-
-
     pub fn construct_transcription(phone_description: Phonet) -> String
     {
         match phone_description
@@ -315,10 +327,10 @@ pub mod international_phonetic_alphabet
                 // otherwise
                 // it will try to represent it in IPA with more than
                 // one character
-                let simple_result = construct_transcription1(phone_description.clone());
+                let simple_result = construct_transcription1(phone_description);
                 if simple_result == " "
                 {
-                    construct_transcription2(phone_description.clone())
+                    construct_transcription2(phone_description)
                 }
                 else
                 {
@@ -376,10 +388,10 @@ pub mod international_phonetic_alphabet
 
             c @ Consonant {vocal_folds: Voiced, place: _, manner: _, airstream: PulmonicEgressive} => construct_unaspirated_pulmonic_egressive(c),
 
-            c @ Consonant {vocal_folds: VoicedAspirated,  place: _, manner: _, airstream: PulmonicEgressive} => construct_unaspirated_pulmonic_egressive(deaspirate(c)) + "ʰ",
+            c @ Consonant {vocal_folds: VoicedAspirated,  place: _, manner: _, airstream: PulmonicEgressive} => construct_unaspirated_pulmonic_egressive(deaspirated_phonet(c)) + "ʰ",
             c @ Consonant {vocal_folds: Voiceless, place: _, manner: _, airstream: PulmonicEgressive} => construct_unaspirated_pulmonic_egressive(c),
 
-            c @ Consonant {vocal_folds: VoicelessAspirated, place: _, manner: _, airstream: PulmonicEgressive} => construct_unaspirated_pulmonic_egressive(deaspirate(c)) + "ʰ",
+            c @ Consonant {vocal_folds: VoicelessAspirated, place: _, manner: _, airstream: PulmonicEgressive} => construct_unaspirated_pulmonic_egressive(deaspirated_phonet(c)) + "ʰ",
 
            // Close Vowels:
            Vowel { height: Close    , backness: Front  , rounding: Unrounded       , vocal_folds: Voiced} => String::from("i"),
@@ -457,6 +469,15 @@ pub mod international_phonetic_alphabet
     }
 
 
+    /// Whether construct_transcription can write a phonet
+    /// in IPA, without leaving a blank or the empty set symbol.
+    pub fn can_transcribe(phonet: Phonet) -> bool
+    {
+        let transcription = construct_transcription(phonet);
+        !transcription.is_empty() && !transcription.contains(' ') && !transcription.contains('∅')
+    }
+
+
     pub fn voiced_transcription(x: String) -> String
     {
         construct_transcription(voiced_phonet(analyze_transcription(x)))
@@ -484,6 +505,13 @@ pub mod international_phonetic_alphabet
             assert_eq!(voiced_transcription(String::from("s")), "z");
         }
 
+        #[test]
+        fn can_transcribe_test()
+        {
+            assert!(can_transcribe(analyze_transcription(String::from("k"))));
+            assert!(!can_transcribe(Consonant {vocal_folds: Voiced, place: LabialVelar, manner: Plosive, airstream: PulmonicEgressive}));
+        }
+
         #[test]
         fn spirantized_transcription_test_voiceless_alveolar_spirantized()
         {
@@ -1 +1,2 @@
 pub mod international_phonetic_alphabet;
+pub mod transcription;
@@ -0,0 +1,284 @@
+pub mod transcription
+{
+    use crate::lib::{Phonet::*, VocalFolds::*, Place::*, Manner::*, Airstream::*};
+    use crate::segment::segment::{Segment, SecondaryArticulation, SecondaryArticulation::*, Length, Length::*, Stress, Stress::*};
+    use crate::grapheme::international_phonetic_alphabet::international_phonetic_alphabet::
+        {analyze_transcription, construct_transcription};
+
+    // Characters that are written after a symbol and belong to it,
+    // other than the combining diacritics (which are all in one Unicode block).
+    static MODIFIER_LETTERS: [char; 10] =
+      ['ʰ', 'ʱ', 'ʷ', 'ʲ', 'ˠ', 'ˤ', 'ⁿ', 'ˡ', 'ː', 'ˑ'];
+
+    // The tie bars, above and below, which join two symbols into one
+    // segment (for example an affricate).
+    static TIE_BARS: [char; 2] = ['͡', '͜'];
+
+    // Written before a segment that is preaspirated.
+    const PREASPIRATION: char = 'ʰ';
+
+    // Written before the first segment of a stressed syllable.
+    const PRIMARY_STRESS: char = 'ˈ';
+    const SECONDARY_STRESS: char = 'ˌ';
+
+
+    /// Whether a character modifies the symbol before it,
+    /// instead of starting a new segment.
+    fn is_modifier(character: char) -> bool
+    {
+        ('\u{0300}'..='\u{036F}').contains(&character) || MODIFIER_LETTERS.contains(&character)
+    }
+
+    // Vowels cannot be aspirated, so a ʰ after a vowel
+    // (or at the start) preaspirates the segment after it.
+    fn is_preaspiration(character: char, last: Option<&String>) -> bool
+    {
+        character == PREASPIRATION && match last
+        {
+            Some(grapheme) => matches!(analyze_transcription(grapheme.clone()), Vowel {..}),
+            None           => true,
+        }
+    }
+
+    // A ʰ taken as preaspiration with no segment after it, at the end
+    // of a word, goes with the segment before it instead, if any.
+    fn attach_final_preaspiration(graphemes: &mut Vec<String>)
+    {
+        if graphemes.last().map_or(false, |x| *x == PREASPIRATION.to_string())
+        {
+            graphemes.pop();
+            if let Some(last) = graphemes.last_mut()
+            {
+                last.push(PREASPIRATION);
+            }
+        }
+    }
+
+    /// Splits a transcription into the graphemes of its segments.
+    /// Diacritics stay with the symbol they are written on,
+    /// and symbols joined by a tie bar stay together.
+    /// A ʰ that starts a word or follows a vowel stays with
+    /// the symbol after it, as preaspiration, and so does a stress mark.
+    /// A ʰ at the end of a word stays with the symbol before it.
+    /// Spaces are skipped.
+    pub fn split_graphemes(text: &str) -> Vec<String>
+    {
+        let mut graphemes: Vec<String> = Vec::new();
+        let mut joining = false;
+        for character in text.chars()
+        {
+            if character.is_whitespace()
+            {
+                attach_final_preaspiration(&mut graphemes);
+                joining = false;
+                continue;
+            }
+            if !joining && (stress_of(character).is_some() || is_preaspiration(character, graphemes.last()))
+            {
+                graphemes.push(character.to_string());
+                joining = true;
+                continue;
+            }
+            match graphemes.last_mut()
+            {
+                Some(last) if joining || is_modifier(character) =>
+                {
+                    last.push(character);
+                    joining = TIE_BARS.contains(&character);
+                },
+                _ =>
+                {
+                    graphemes.push(character.to_string());
+                    joining = false;
+                },
+            }
+        }
+        attach_final_preaspiration(&mut graphemes);
+        graphemes
+    }
+
+
+    fn secondary_articulation_of(character: char) -> Option<SecondaryArticulation>
+    {
+        match character
+        {
+            'ʷ' => Some(Labialized),
+            'ʲ' => Some(Palatalized),
+            'ˠ' => Some(Velarized),
+            'ˤ' => Some(Pharyngealized),
+            _   => None,
+        }
+    }
+
+    fn secondary_articulation_symbol(secondary: SecondaryArticulation) -> &'static str
+    {
+        match secondary
+        {
+            Plain          => "",
+            Labialized     => "ʷ",
+            Palatalized    => "ʲ",
+            Velarized      => "ˠ",
+            Pharyngealized => "ˤ",
+        }
+    }
+
+    fn stress_of(character: char) -> Option<Stress>
+    {
+        match character
+        {
+            PRIMARY_STRESS   => Some(Primary),
+            SECONDARY_STRESS => Some(Secondary),
+            _                => None,
+        }
+    }
+
+    fn stress_symbol(stress: Stress) -> String
+    {
+        match stress
+        {
+            Unstressed => String::new(),
+            Primary    => PRIMARY_STRESS.to_string(),
+            Secondary  => SECONDARY_STRESS.to_string(),
+        }
+    }
+
+    fn length_of(character: char) -> Option<Length>
+    {
+        match character
+        {
+            'ː' => Some(Long),
+            'ˑ' => Some(HalfLong),
+            _   => None,
+        }
+    }
+
+    fn length_symbol(length: Length) -> &'static str
+    {
+        match length
+        {
+            Short    => "",
+            HalfLong => "ˑ",
+            Long     => "ː",
+        }
+    }
+
+    /// Analyzes the grapheme of one segment, including
+    /// the superscript for a secondary articulation
+    /// and the length mark, if any.
+    pub fn analyze_segment(grapheme: &str) -> Segment
+    {
+        let mut characters = grapheme.chars().peekable();
+        let stress = characters.peek().and_then(|x| stress_of(*x)).unwrap_or(Unstressed);
+        if stress != Unstressed
+        {
+            characters.next();
+        }
+        let preaspirated = characters.next_if_eq(&PREASPIRATION).is_some();
+        let mut secondary = Plain;
+        let mut length = Short;
+        let mut base = String::new();
+        for character in characters
+        {
+            match (secondary_articulation_of(character), length_of(character))
+            {
+                (Some(x), _) => secondary = x,
+                (_, Some(x)) => length = x,
+                _            => base.push(character),
+            }
+        }
+        // The dark l has a symbol of its own.
+        if base == "ɫ"
+        {
+            base = String::from("l");
+            secondary = Velarized;
+        }
+        Segment {phonet: analyze_transcription(base), secondary, length, preaspirated, stress}
+    }
+
+    /// Analyzes a transcription of several segments,
+    /// for example "kʷat͡ʃ" becomes [kʷ, a, t͡ʃ].
+    pub fn parse_transcription(text: &str) -> Vec<Segment>
+    {
+        split_graphemes(text).iter().map(|grapheme| analyze_segment(grapheme)).collect()
+    }
+
+    /// Writes one segment in IPA.
+    pub fn construct_segment_transcription(segment: Segment) -> String
+    {
+        let mut prefix = stress_symbol(segment.stress);
+        if segment.preaspirated
+        {
+            prefix.push(PREASPIRATION);
+        }
+        match segment
+        {
+            Segment {phonet: Consonant {vocal_folds: Voiced, place: Alveolar, manner: LateralApproximant, airstream: PulmonicEgressive}, secondary: Velarized, length, ..} =>
+              prefix + "ɫ" + length_symbol(length),
+            _ => prefix + &construct_transcription(segment.phonet) + secondary_articulation_symbol(segment.secondary) + length_symbol(segment.length),
+        }
+    }
+
+    /// Writes several segments in IPA, one after the other.
+    pub fn construct_segments_transcription(segments: &[Segment]) -> String
+    {
+        segments.iter().map(|segment| construct_segment_transcription(*segment)).collect()
+    }
+
+
+    #[cfg(test)]
+    mod transcription_tests
+    {
+        use super::*;
+        use crate::segment::segment::deaspirated_segment;
+
+        #[test]
+        fn split_graphemes_test_diacritics_and_tie_bars()
+        {
+            assert_eq!(split_graphemes("kʷat͡ʃ d̥"), vec!["kʷ", "a", "t͡ʃ", "d̥"]);
+        }
+
+        #[test]
+        fn parse_transcription_test_preaspiration()
+        {
+            let segments = parse_transcription("ʰpaʰta");
+            assert!(segments[0].preaspirated && segments[2].preaspirated);
+            assert_eq!(construct_segments_transcription(&segments), "ʰpaʰta");
+        }
+
+        #[test]
+        fn parse_transcription_test_final_aspiration_after_vowel()
+        {
+            assert_eq!(split_graphemes("taʰ taʰ"), vec!["t", "aʰ", "t", "aʰ"]);
+            let segments = parse_transcription("taʰ");
+            assert_eq!(segments.len(), 2);
+            assert!(!segments[1].preaspirated);
+            assert_eq!(construct_segments_transcription(&segments), "ta");
+            assert!(split_graphemes("ʰ").is_empty());
+        }
+
+        #[test]
+        fn parse_transcription_test_breathy_voiced_aspiration()
+        {
+            let segments = parse_transcription("bʱa");
+            assert_eq!(segments[0].phonet, Consonant {vocal_folds: VoicedAspirated, place: Bilabial, manner: Plosive, airstream: PulmonicEgressive});
+            assert_eq!(construct_segments_transcription(&[deaspirated_segment(segments[0])]), "b");
+        }
+
+        #[test]
+        fn parse_transcription_test_stress()
+        {
+            let text = "ɪnˈsaɪd ˌɫɪtəl";
+            let segments = parse_transcription(text);
+            assert_eq!(segments[2].stress, Primary);
+            assert_eq!(segments[6].stress, Secondary);
+            assert_eq!(construct_segments_transcription(&segments), "ɪnˈsaɪdˌɫɪtəl");
+        }
+
+        #[test]
+        fn parse_transcription_test_round_trip()
+        {
+            let text = "kʷaːt͡ʃɫ";
+            assert_eq!(construct_segments_transcription(&parse_transcription(text)), text);
+        }
+    }
+}
@@ -1,5 +1,24 @@
+#![allow(clippy::module_inception)]
+// map_or is used where clippy would suggest is_none_or or is_some_and,
+// which need a newer Rust than the crate otherwise does.
+#![allow(clippy::unnecessary_map_or)]
+
+pub mod analogy;
+pub mod feature_diff;
+pub mod grapheme;
+pub mod optimality;
+pub mod plausibility;
+pub mod process;
+pub mod rule;
+pub mod segment;
+pub mod table;
+pub mod transformation;
+
 pub mod lib
 {
+    use std::fmt;
+    use crate::grapheme::international_phonetic_alphabet::international_phonetic_alphabet::can_transcribe;
+    use crate::plausibility::plausibility::{plausibility, Plausibility::ChartImpossible};
     use Phonet::*;
     use VocalFolds::*;
     use Place::*;
@@ -8,8 +27,9 @@ pub mod lib
     use Height::*;
     use Backness::*;
     use Rounding::*;
+    use PlaceGroup::*;
 
-    #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+    #[derive(Eq, PartialEq, Hash, Debug, Clone, Copy)]
     pub enum Phonet
     {
         Consonant
@@ -31,35 +51,35 @@ pub mod lib
 
 
 
-    #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+    #[derive(Eq, PartialEq, Hash, Debug, Clone, Copy)]
     pub enum Backness
     {
          Front , Central , Back , UnmarkedBackness
     }
 
 
-    static BACKNESS_STATES: [Backness; 3] = [Front, Central, Back];
+    pub static BACKNESS_STATES: [Backness; 3] = [Front, Central, Back];
 
-    #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+    #[derive(Eq, PartialEq, Hash, Debug, Clone, Copy)]
     pub enum Height
     { 
       Close , NearClose , CloseMid ,
       Mid , OpenMid , NearOpen , Open , UnmarkedHeight
     }
 
-    static HEIGHT_STATES: [Height; 7] = 
+    pub static HEIGHT_STATES: [Height; 7] = 
       [Close, NearClose, CloseMid, Mid, OpenMid, NearOpen, Open];
 
-    #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+    #[derive(Eq, PartialEq, Hash, Debug, Clone, Copy)]
     pub enum Rounding
     {
         Rounded , Unrounded , UnmarkedRounding
     }
 
-    static ROUNDING_STATES: [Rounding; 2] = [Rounded, Unrounded];
+    pub static ROUNDING_STATES: [Rounding; 2] = [Rounded, Unrounded];
 
 
-    #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+    #[derive(Eq, PartialEq, Hash, Debug, Clone, Copy)]
     pub enum Place
     {
         Bilabial, LabioDental, Dental, Alveolar, PostAlveolar,
@@ -74,7 +94,7 @@ pub mod lib
         UnmarkedPlace
     }
 
-    static PLACE_STATES: [Place; 16]
+    pub static PLACE_STATES: [Place; 16]
        = [ Bilabial, LabioDental, Dental, Alveolar, PostAlveolar
                   , Retroflex
                   , Palatal  , Velar  , Uvular , Pharyngeal , Glottal , Epiglottal
@@ -82,25 +102,126 @@ pub mod lib
                   , PalatoAlveolar
                   ];
     
-    pub fn retracted_place(place: Place) -> Place
+    /// The places of a single articulation, from the front of the mouth
+    /// to the back. PalatoAlveolar is left out, as it is at the same
+    /// position as PostAlveolar.
+    pub static PLACE_ORDER: [Place; 13]
+       = [ Bilabial, LabioDental, Dental, Alveolar, PostAlveolar
+         , Retroflex, AlveoloPalatal, Palatal, Velar, Uvular
+         , Pharyngeal, Epiglottal, Glottal
+         ];
+
+    // The positions in PLACE_ORDER where a place is articulated.
+    // A double articulation has two, the lips and the tongue body.
+    fn place_positions(place: Place) -> &'static [usize]
     {
         match place
         {
-            Bilabial => LabioDental,
-            LabioDental => Dental,
-            Dental => Alveolar,
-            Alveolar => PostAlveolar,
-            PostAlveolar => Retroflex,
-            Retroflex => Palatal,
-            Palatal => Velar,
-            Velar => Uvular,
-            Uvular => Pharyngeal,
-            Pharyngeal => Glottal,
-            same => same,
+            Bilabial       => &[0],
+            LabioDental    => &[1],
+            Dental         => &[2],
+            Alveolar       => &[3],
+            PostAlveolar   => &[4],
+            PalatoAlveolar => &[4],
+            Retroflex      => &[5],
+            AlveoloPalatal => &[6],
+            Palatal        => &[7],
+            Velar          => &[8],
+            Uvular         => &[9],
+            Pharyngeal     => &[10],
+            Epiglottal     => &[11],
+            Glottal        => &[12],
+            LabialPalatal  => &[0, 7],
+            LabialVelar    => &[0, 8],
+            UnmarkedPlace  => &[],
+        }
+    }
+
+    // Moves a place one step along PLACE_ORDER. For a double
+    // articulation the tongue body moves and the lips stay, so
+    // LabialPalatal and LabialVelar move into each other.
+    fn shifted_place(place: Place, back: bool) -> Place
+    {
+        match (place, back)
+        {
+            (LabialPalatal, true)  => LabialVelar,
+            (LabialVelar, false)   => LabialPalatal,
+            (LabialPalatal, false) |
+            (LabialVelar, true)    => place,
+            _ => match place_positions(place).first()
+            {
+                Some(&i) if back && i + 1 < PLACE_ORDER.len() => PLACE_ORDER[i + 1],
+                Some(&i) if !back && i > 0                    => PLACE_ORDER[i - 1],
+                _                                             => place,
+            },
         }
     }
 
+    /// Moves a place of articulation one step back (see PLACE_ORDER).
+    /// Glottal, and an unmarked place, stay as they are.
+    pub fn retracted_place(place: Place) -> Place
+    {
+        shifted_place(place, true)
+    }
+
+    /// Moves a place of articulation one step forward (see PLACE_ORDER).
+    /// Bilabial, and an unmarked place, stay as they are.
+    pub fn advanced_place(place: Place) -> Place
+    {
+        shifted_place(place, false)
+    }
+
+    /// How many steps apart two places are in PLACE_ORDER.
+    /// A double articulation counts from whichever of its two
+    /// articulations is closer, so Bilabial and LabialVelar are 0 apart.
+    /// Returns None if either place is unmarked.
+    pub fn place_distance(a: Place, b: Place) -> Option<usize>
+    {
+        let mut distances = place_positions(a).iter()
+                              .flat_map(|x| place_positions(b).iter().map(move |y| x.abs_diff(*y)));
+        distances.next().map(|first| distances.fold(first, usize::min))
+    }
+
+    /// The articulators that places of articulation are grouped by.
     #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+    pub enum PlaceGroup
+    {
+        Labial,     // The lips.
+        Coronal,    // The tip or blade of the tongue.
+        Dorsal,     // The body of the tongue.
+        Radical,    // The root of the tongue, or the epiglottis.
+        Laryngeal   // The larynx.
+    }
+
+    pub static PLACE_GROUP_STATES: [PlaceGroup; 5] =
+      [Labial, Coronal, Dorsal, Radical, Laryngeal];
+
+    /// The groups a place of articulation belongs to.
+    /// Most places are in one group, double articulations are in two,
+    /// and an unmarked place is in none.
+    pub fn place_groups(place: Place) -> &'static [PlaceGroup]
+    {
+        match place
+        {
+            Bilabial | LabioDental                            => &[Labial],
+            Dental | Alveolar | PostAlveolar | PalatoAlveolar
+          | Retroflex | AlveoloPalatal                        => &[Coronal],
+            Palatal | Velar | Uvular                          => &[Dorsal],
+            Pharyngeal | Epiglottal                           => &[Radical],
+            Glottal                                           => &[Laryngeal],
+            LabialPalatal | LabialVelar                       => &[Labial, Dorsal],
+            UnmarkedPlace                                     => &[],
+        }
+    }
+
+    /// Whether two places share a group, for example Alveolar and Retroflex
+    /// (both coronal) or LabialVelar and Bilabial (both labial).
+    pub fn same_place_group(a: Place, b: Place) -> bool
+    {
+        place_groups(a).iter().any(|group| place_groups(b).contains(group))
+    }
+
+    #[derive(Eq, PartialEq, Hash, Debug, Clone, Copy)]
     pub enum Manner
     { Plosive , Nasal , Trill , TapOrFlap , Approximant , Fricative
                   , Affricate 
@@ -111,7 +232,7 @@ pub mod lib
                   , UnmarkedManner // There are very few IPA symbols for lateral flaps
     }
 
-    static MANNER_STATES: [Manner; 11]
+    pub static MANNER_STATES: [Manner; 11]
       = [ Plosive
         , Nasal
         , Trill
@@ -125,29 +246,29 @@ pub mod lib
         , Lateral
         ];
 
-    #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+    #[derive(Eq, PartialEq, Hash, Debug, Clone, Copy)]
     pub enum Airstream
     {
         PulmonicEgressive , Click , Implosive , UnmarkedAirstream
     }
 
-    static AIRSTREAM_STATES: [Airstream; 3]
+    pub static AIRSTREAM_STATES: [Airstream; 3]
        = [PulmonicEgressive, 
           Click, 
           Implosive
          ];
 
-    #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+    #[derive(Eq, PartialEq, Hash, Debug, Clone, Copy)]
     pub enum VocalFolds
     {
         Voiced , Voiceless , VoicedAspirated , VoicelessAspirated , CreakyVoiced, UnmarkedVocalFolds
     }
 
-    static VOCAL_FOLD_STATES: [VocalFolds; 5] =
+    pub static VOCAL_FOLD_STATES: [VocalFolds; 5] =
       [Voiceless, Voiced, VoicedAspirated, VoicelessAspirated, CreakyVoiced];
 
 
-    pub struct PhonetInventory([Phonet]);
+    pub struct PhonetInventory(pub [Phonet]);
 
     // A function that given an IPA symbol will convert it to the voiced equivalent.
     pub fn voiced_phonet(phonete: Phonet) -> Phonet
@@ -180,6 +301,33 @@ pub mod lib
     }
 
 
+    /// Aspirates a consonant: p becomes pʰ, and b becomes bʰ
+    /// (which is VoicedAspirated). Consonants that are already aspirated,
+    /// creaky or unmarked for voicing stay as they are, and so do vowels,
+    /// as an aspirated vowel cannot be represented.
+    pub fn aspirated_phonet(phonet: Phonet) -> Phonet
+    {
+        match phonet
+        {
+            Consonant {vocal_folds: Voiceless, place, manner, airstream} => Consonant {vocal_folds: VoicelessAspirated, place, manner, airstream},
+            Consonant {vocal_folds: Voiced   , place, manner, airstream} => Consonant {vocal_folds: VoicedAspirated   , place, manner, airstream},
+            other => other,
+        }
+    }
+
+    /// Removes the aspiration of a consonant, keeping its voicing:
+    /// pʰ becomes p and bʱ becomes b. Anything else stays as it is.
+    pub fn deaspirated_phonet(phonet: Phonet) -> Phonet
+    {
+        match phonet
+        {
+            Consonant {vocal_folds: VoicelessAspirated, place, manner, airstream} => Consonant {vocal_folds: Voiceless, place, manner, airstream},
+            Consonant {vocal_folds: VoicedAspirated   , place, manner, airstream} => Consonant {vocal_folds: Voiced   , place, manner, airstream},
+            other => other,
+        }
+    }
+
+
     pub fn spirantized_phonet(phonet: Phonet) -> Phonet
     {
         match phonet
@@ -226,218 +374,454 @@ pub mod lib
             => unmark_differences(v, c) // Change the order of arguments
         }
     }
-    
 
-        
 
+    /// A feature of a phonet, that is one of the fields
+    /// of a consonant or a vowel.
+    #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+    pub enum Feature
+    {
+        VocalFolds, Place, Manner, Airstream, Height, Backness, Rounding
+    }
 
-    pub fn generate_from_unmarked(phonet: Phonet) -> Vec<Phonet>
+    static CONSONANT_FEATURES: [Feature; 4] =
+      [Feature::VocalFolds, Feature::Place, Feature::Manner, Feature::Airstream];
+
+    static VOWEL_FEATURES: [Feature; 4] =
+      [Feature::Height, Feature::Backness, Feature::Rounding, Feature::VocalFolds];
+
+    pub static ALL_FEATURES: [Feature; 7] =
+      [ Feature::VocalFolds, Feature::Place, Feature::Manner, Feature::Airstream
+      , Feature::Height, Feature::Backness, Feature::Rounding
+      ];
+
+    impl fmt::Display for Feature
+    {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            let name = match self
+            {
+                Feature::VocalFolds => "vocal_folds",
+                Feature::Place      => "place",
+                Feature::Manner     => "manner",
+                Feature::Airstream  => "airstream",
+                Feature::Height     => "height",
+                Feature::Backness   => "backness",
+                Feature::Rounding   => "rounding",
+            };
+            write!(f, "{}", name)
+        }
+    }
+
+    /// The value that a phonet has for one of its features.
+    #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+    pub enum FeatureValue
+    {
+        VocalFolds(VocalFolds),
+        Place(Place),
+        Manner(Manner),
+        Airstream(Airstream),
+        Height(Height),
+        Backness(Backness),
+        Rounding(Rounding)
+    }
+
+    impl FeatureValue
+    {
+        /// The feature that this is a value of.
+        pub fn feature(self) -> Feature
+        {
+            match self
+            {
+                FeatureValue::VocalFolds(_) => Feature::VocalFolds,
+                FeatureValue::Place(_)      => Feature::Place,
+                FeatureValue::Manner(_)     => Feature::Manner,
+                FeatureValue::Airstream(_)  => Feature::Airstream,
+                FeatureValue::Height(_)     => Feature::Height,
+                FeatureValue::Backness(_)   => Feature::Backness,
+                FeatureValue::Rounding(_)   => Feature::Rounding,
+            }
+        }
+    }
+
+    impl fmt::Display for FeatureValue
+    {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            match self
+            {
+                FeatureValue::VocalFolds(x) => write!(f, "{:?}", x),
+                FeatureValue::Place(x)      => write!(f, "{:?}", x),
+                FeatureValue::Manner(x)     => write!(f, "{:?}", x),
+                FeatureValue::Airstream(x)  => write!(f, "{:?}", x),
+                FeatureValue::Height(x)     => write!(f, "{:?}", x),
+                FeatureValue::Backness(x)   => write!(f, "{:?}", x),
+                FeatureValue::Rounding(x)   => write!(f, "{:?}", x),
+            }
+        }
+    }
+
+    /// The features that a phonet has, consonants and vowels
+    /// do not share all of their features.
+    pub fn features_of(phonet: Phonet) -> &'static [Feature]
     {
         match phonet
         {
-            Consonant{vocal_folds: voice1, place: place1, manner: manner1, airstream: airstream1}
-            =>
+            Consonant {..} => &CONSONANT_FEATURES,
+            Vowel     {..} => &VOWEL_FEATURES,
+        }
+    }
+
+    /// Returns the value of a feature of a phonet, or None
+    /// if the phonet does not have that feature
+    /// (for example the place of articulation of a vowel).
+    pub fn feature_value(phonet: Phonet, feature: Feature) -> Option<FeatureValue>
+    {
+        match (phonet, feature)
+        {
+            (Consonant {vocal_folds: x, ..}, Feature::VocalFolds) => Some(FeatureValue::VocalFolds(x)),
+            (Consonant {place: x, ..}      , Feature::Place)      => Some(FeatureValue::Place(x)),
+            (Consonant {manner: x, ..}     , Feature::Manner)     => Some(FeatureValue::Manner(x)),
+            (Consonant {airstream: x, ..}  , Feature::Airstream)  => Some(FeatureValue::Airstream(x)),
+            (Vowel {vocal_folds: x, ..}    , Feature::VocalFolds) => Some(FeatureValue::VocalFolds(x)),
+            (Vowel {height: x, ..}         , Feature::Height)     => Some(FeatureValue::Height(x)),
+            (Vowel {backness: x, ..}       , Feature::Backness)   => Some(FeatureValue::Backness(x)),
+            (Vowel {rounding: x, ..}       , Feature::Rounding)   => Some(FeatureValue::Rounding(x)),
+            _ => None,
+        }
+    }
+
+    /// Returns the phonet with one feature changed to the given value,
+    /// or None if the phonet does not have that feature.
+    pub fn with_feature_value(phonet: Phonet, value: FeatureValue) -> Option<Phonet>
+    {
+        match (phonet, value)
+        {
+            (Consonant {place, manner, airstream, ..}, FeatureValue::VocalFolds(x))    => Some(Consonant {vocal_folds: x, place, manner, airstream}),
+            (Consonant {vocal_folds, manner, airstream, ..}, FeatureValue::Place(x))   => Some(Consonant {vocal_folds, place: x, manner, airstream}),
+            (Consonant {vocal_folds, place, airstream, ..}, FeatureValue::Manner(x))   => Some(Consonant {vocal_folds, place, manner: x, airstream}),
+            (Consonant {vocal_folds, place, manner, ..}, FeatureValue::Airstream(x))   => Some(Consonant {vocal_folds, place, manner, airstream: x}),
+            (Vowel {height, backness, rounding, ..}, FeatureValue::VocalFolds(x))      => Some(Vowel {height, backness, rounding, vocal_folds: x}),
+            (Vowel {backness, rounding, vocal_folds, ..}, FeatureValue::Height(x))     => Some(Vowel {height: x, backness, rounding, vocal_folds}),
+            (Vowel {height, rounding, vocal_folds, ..}, FeatureValue::Backness(x))     => Some(Vowel {height, backness: x, rounding, vocal_folds}),
+            (Vowel {height, backness, vocal_folds, ..}, FeatureValue::Rounding(x))     => Some(Vowel {height, backness, rounding: x, vocal_folds}),
+            _ => None,
+        }
+    }
+
+    /// A feature that did not have the same value
+    /// in every phonet, along with the values that were seen
+    /// (in the order in which they were first seen).
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub struct FeatureVariation
+    {
+        pub feature: Feature,
+        pub values: Vec<FeatureValue>
+    }
+
+    impl fmt::Display for FeatureVariation
+    {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            let values: Vec<String> = self.values.iter().map(|v| v.to_string()).collect();
+            write!(f, "{} varied: {}", self.feature, values.join(", "))
+        }
+    }
+
+    /// The shared (underspecified) description of several phonets,
+    /// and an account of which features were left unmarked and why.
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub struct Generalization
+    {
+        pub phonet: Phonet,
+        pub variations: Vec<FeatureVariation>,
+
+        // Whether there were both consonants and vowels.
+        // If so the features that only consonants or only vowels
+        // have are all unmarked, even if they did not vary.
+        pub mixes_consonants_and_vowels: bool
+    }
+
+    impl fmt::Display for Generalization
+    {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            let mut lines: Vec<String> = Vec::new();
+            if self.mixes_consonants_and_vowels
             {
-                let mut voice2: Vec<VocalFolds>;
-                if voice1  == UnmarkedVocalFolds
-                {
-                    voice2 = Vec::new();
-                    
-                    for v in VOCAL_FOLD_STATES.iter()
-                    {
-                        voice2.push(*v);
-                    }
-                }
-                else
-                {
-                    voice2 = Vec::new();
-                    voice2.push(voice1);
-                }
-                
-                let mut place2: Vec<Place>;
-                if place1  == UnmarkedPlace
-                {
-                    place2 = Vec::new();
-                    
-                    for p in PLACE_STATES.iter()
-                    {
-                        place2.push(*p);
-                    }
-                }
-                else
-                {
-                    place2 = Vec::new();
-                    place2.push(place1);
-                }
-                
+                lines.push(String::from("both consonants and vowels were present"));
+            }
+            for variation in self.variations.iter()
+            {
+                lines.push(variation.to_string());
+            }
+            if lines.is_empty()
+            {
+                lines.push(String::from("no features varied"));
+            }
+            write!(f, "{}", lines.join("\n"))
+        }
+    }
 
+    /// Like unmark_differences but for any number of phonets.
+    /// Returns None if there are no phonets.
+    pub fn unmark_all_differences(phonets: &[Phonet]) -> Option<Generalization>
+    {
+        let (first, rest) = phonets.split_first()?;
+        let shared = rest.iter().fold(*first, |acc, p| unmark_differences(acc, *p));
 
-                let mut manner2: Vec<Manner>;
-                if manner1  == UnmarkedManner
-                {
-                    manner2 = Vec::new();
-                    
-                    for m in MANNER_STATES.iter()
-                    {
-                        manner2.push(*m);
-                    }
-                }
-                else
+        let mut variations: Vec<FeatureVariation> = Vec::new();
+        for feature in ALL_FEATURES.iter()
+        {
+            let mut values: Vec<FeatureValue> = Vec::new();
+            for value in phonets.iter().filter_map(|p| feature_value(*p, *feature))
+            {
+                if !values.contains(&value)
                 {
-                    manner2 = Vec::new();
-                    manner2.push(manner1);
+                    values.push(value);
                 }
+            }
+            if values.len() > 1
+            {
+                variations.push(FeatureVariation {feature: *feature, values});
+            }
+        }
 
+        let has_consonant = phonets.iter().any(|p| matches!(p, Consonant {..}));
+        let has_vowel     = phonets.iter().any(|p| matches!(p, Vowel {..}));
 
+        Some(Generalization { phonet: shared
+                            , variations
+                            , mixes_consonants_and_vowels: has_consonant && has_vowel
+                            })
+    }
+    
 
+        
 
-                let mut airstream2: Vec<Airstream>;
-                if airstream1  == UnmarkedAirstream
-                {
-                    airstream2 = Vec::new();
-                    
-                    for a in AIRSTREAM_STATES.iter()
-                    {
-                        airstream2.push(*a);
-                    }
-                }
-                else
-                {
-                    airstream2 = Vec::new();
-                    airstream2.push(airstream1);
-                }
 
-                
-                let mut possibilities: Vec<Phonet> = Vec::new();
+    /// Lists every phonet that an underspecified phonet could stand for,
+    /// by trying every state of each unmarked feature.
+    pub fn generate_from_unmarked(phonet: Phonet) -> Vec<Phonet>
+    {
+        expand_unmarked(phonet).collect()
+    }
 
-                for p in place2.iter()
-                {
-                    for v in voice2.iter()
-                    {
-                        for m in manner2.iter()
-                        {
-                            for a in airstream2.iter()
-                            {
-                                possibilities.push(Consonant{vocal_folds: *v, place: *p, manner: *m, airstream: *a});
-                            }
-                        }
-                    }
-                }
-                possibilities
-            },
-           Vowel{height: height1, backness: backness1, rounding: rounding1, vocal_folds: voice1}
-            =>
+
+    /// A lazy enumeration of the phonets that an underspecified phonet
+    /// could stand for. Nothing is allocated; each phonet is computed
+    /// from a counter when it is asked for. The results can be narrowed with
+    /// `excluding_impossible`, `transcribable` and `within`.
+    #[derive(Debug, Clone, Copy)]
+    pub struct UnmarkedExpansion<'a>
+    {
+        phonet: Phonet,
+        index: usize,
+        total: usize,
+        exclude_impossible: bool,
+        transcribable_only: bool,
+        inventory: Option<&'a [Phonet]>
+    }
+
+    /// Starts a lazy enumeration of the phonets that an underspecified
+    /// phonet could stand for. Yields the same phonets in the same order
+    /// as generate_from_unmarked.
+    pub fn expand_unmarked(phonet: Phonet) -> UnmarkedExpansion<'static>
+    {
+        UnmarkedExpansion { phonet
+                          , index: 0
+                          , total: expansion_radices(phonet).iter().product()
+                          , exclude_impossible: false
+                          , transcribable_only: false
+                          , inventory: None
+                          }
+    }
+
+    impl<'a> UnmarkedExpansion<'a>
+    {
+        /// Skip phonets that are impossible according to the IPA chart.
+        pub fn excluding_impossible(self) -> UnmarkedExpansion<'a>
+        {
+            UnmarkedExpansion {exclude_impossible: true, ..self}
+        }
+
+        /// Skip phonets that cannot be written in IPA.
+        pub fn transcribable(self) -> UnmarkedExpansion<'a>
+        {
+            UnmarkedExpansion {transcribable_only: true, ..self}
+        }
+
+        /// Skip phonets that are not in the given inventory.
+        pub fn within<'b>(self, inventory: &'b [Phonet]) -> UnmarkedExpansion<'b>
+        {
+            UnmarkedExpansion { phonet: self.phonet
+                              , index: self.index
+                              , total: self.total
+                              , exclude_impossible: self.exclude_impossible
+                              , transcribable_only: self.transcribable_only
+                              , inventory: Some(inventory)
+                              }
+        }
+
+        fn accepts(&self, candidate: Phonet) -> bool
+        {
+            (!self.exclude_impossible || !impossible(candidate))
+            && (!self.transcribable_only || can_transcribe(candidate))
+            && self.inventory.map_or(true, |inventory| inventory.contains(&candidate))
+        }
+    }
+
+    impl Iterator for UnmarkedExpansion<'_>
+    {
+        type Item = Phonet;
+
+        fn next(&mut self) -> Option<Phonet>
+        {
+            while self.index < self.total
             {
-                let mut voice2: Vec<VocalFolds>;
-                if voice1  == UnmarkedVocalFolds
-                {
-                    voice2 = Vec::new();
-                    
-                    for v in VOCAL_FOLD_STATES.iter()
-                    {
-                        voice2.push(*v);
-                    }
-                }
-                else
-                {
-                    voice2 = Vec::new();
-                    voice2.push(voice1);
-                }
-                
-                let mut height2: Vec<Height>;
-                if height1   == UnmarkedHeight
-                {
-                    height2 = Vec::new();
-                    
-                    for h in HEIGHT_STATES.iter()
-                    {
-                        height2.push(*h);
-                    }
-                }
-                else
+                let candidate = nth_expansion(self.phonet, self.index);
+                self.index += 1;
+                if self.accepts(candidate)
                 {
-                    height2 = Vec::new();
-                    height2.push(height1);
+                    return Some(candidate);
                 }
+            }
+            None
+        }
 
-                let mut backness2: Vec<Backness>;
-                if backness1 == UnmarkedBackness
-                {
-                    backness2 = Vec::new();
-                    
-                    for b in BACKNESS_STATES.iter()
-                    {
-                        backness2.push(*b);
-                    }
-                }
-                else
-                {
-                    backness2 = Vec::new();
-                    backness2.push(backness1);
-                }
+        fn size_hint(&self) -> (usize, Option<usize>)
+        {
+            (0, Some(self.total - self.index))
+        }
+    }
 
+    // The number of choices for each feature, outermost loop first.
+    // Consonants are ordered place, voice, manner, airstream.
+    // Vowels are ordered height, backness, rounding, voice.
+    fn expansion_radices(phonet: Phonet) -> [usize; 4]
+    {
+        fn radix<T: PartialEq>(value: T, unmarked: T, states: &[T]) -> usize
+        {
+            if value == unmarked { states.len() } else { 1 }
+        }
 
+        match phonet
+        {
+            Consonant {vocal_folds, place, manner, airstream} =>
+              [ radix(place, UnmarkedPlace, &PLACE_STATES)
+              , radix(vocal_folds, UnmarkedVocalFolds, &VOCAL_FOLD_STATES)
+              , radix(manner, UnmarkedManner, &MANNER_STATES)
+              , radix(airstream, UnmarkedAirstream, &AIRSTREAM_STATES)
+              ],
+            Vowel {height, backness, rounding, vocal_folds} =>
+              [ radix(height, UnmarkedHeight, &HEIGHT_STATES)
+              , radix(backness, UnmarkedBackness, &BACKNESS_STATES)
+              , radix(rounding, UnmarkedRounding, &ROUNDING_STATES)
+              , radix(vocal_folds, UnmarkedVocalFolds, &VOCAL_FOLD_STATES)
+              ],
+        }
+    }
 
-                let mut rounding2: Vec<Rounding>;
-                if rounding1 == UnmarkedRounding
-                {
-                    rounding2 = Vec::new();
-                    
-                    for r in ROUNDING_STATES.iter()
-                    {
-                        rounding2.push(*r);
-                    }
-                }
-                else
-                {
-                    rounding2 = Vec::new();
-                    rounding2.push(rounding1);
-                }
+    // Computes the phonet at a position in the expansion,
+    // treating the position as a mixed radix number
+    // whose last digit changes fastest.
+    fn nth_expansion(phonet: Phonet, index: usize) -> Phonet
+    {
+        let radices = expansion_radices(phonet);
+        let mut digits = [0; 4];
+        let mut rest = index;
+        for i in (0..4).rev()
+        {
+            digits[i] = rest % radices[i];
+            rest /= radices[i];
+        }
 
-                
-                let mut possibilities: Vec<Phonet> = Vec::new();
-                
-                for h in height2.iter()
-                {
-                    for b in backness2.iter()
-                    {
-                        for r in rounding2.iter()
-                        {
-                            for v in voice2.iter()
-                            {
-                                possibilities.push(Vowel{height: *h, backness: *b, rounding: *r, vocal_folds: *v});
-                            }
-                        }
-                    }
-                }
-                possibilities
-            },
+        fn pick<T: PartialEq + Copy>(value: T, unmarked: T, states: &[T], digit: usize) -> T
+        {
+            if value == unmarked { states[digit] } else { value }
+        }
+
+        match phonet
+        {
+            Consonant {vocal_folds, place, manner, airstream} =>
+              Consonant { place      : pick(place, UnmarkedPlace, &PLACE_STATES, digits[0])
+                        , vocal_folds: pick(vocal_folds, UnmarkedVocalFolds, &VOCAL_FOLD_STATES, digits[1])
+                        , manner     : pick(manner, UnmarkedManner, &MANNER_STATES, digits[2])
+                        , airstream  : pick(airstream, UnmarkedAirstream, &AIRSTREAM_STATES, digits[3])
+                        },
+            Vowel {height, backness, rounding, vocal_folds} =>
+              Vowel { height     : pick(height, UnmarkedHeight, &HEIGHT_STATES, digits[0])
+                    , backness   : pick(backness, UnmarkedBackness, &BACKNESS_STATES, digits[1])
+                    , rounding   : pick(rounding, UnmarkedRounding, &ROUNDING_STATES, digits[2])
+                    , vocal_folds: pick(vocal_folds, UnmarkedVocalFolds, &VOCAL_FOLD_STATES, digits[3])
+                    },
         }
     }
     
 
+
     // The following function returns whether an articulation is
-    // considered impossible according to the IPA (pulmonic) consonants chart.
-    // Does not work for other values.
+    // considered impossible, either because it cannot be articulated
+    // or because it is shaded on the IPA chart.
+    // See the plausibility module for a graded answer with a reason.
     pub fn impossible(phonet: Phonet) -> bool
     {
-        match phonet
+        plausibility(phonet).plausibility <= ChartImpossible
+    }
+
+
+    #[cfg(test)]
+    mod lib_tests
+    {
+        use super::*;
+
+        #[test]
+        fn retracted_place_test_round_trip()
+        {
+            assert_eq!(retracted_place(Pharyngeal), Epiglottal);
+            assert_eq!(retracted_place(Epiglottal), Glottal);
+            assert_eq!(retracted_place(LabialPalatal), LabialVelar);
+            for place in PLACE_ORDER.iter().skip(1)
+            {
+                assert_eq!(retracted_place(advanced_place(*place)), *place);
+            }
+        }
+
+        #[test]
+        fn place_distance_test()
+        {
+            assert_eq!(place_distance(Alveolar, Velar), Some(5));
+            assert_eq!(place_distance(Bilabial, LabialVelar), Some(0));
+            assert_eq!(place_distance(UnmarkedPlace, Velar), None);
+            assert!(same_place_group(Retroflex, Dental));
+            assert!(!same_place_group(Palatal, AlveoloPalatal));
+        }
+
+        #[test]
+        fn unmark_all_differences_test_places_varied()
+        {
+            let p = Consonant {vocal_folds: Voiceless, place: Bilabial, manner: Plosive, airstream: PulmonicEgressive};
+            let t = Consonant {vocal_folds: Voiceless, place: Alveolar, manner: Plosive, airstream: PulmonicEgressive};
+            let k = Consonant {vocal_folds: Voiceless, place: Velar   , manner: Plosive, airstream: PulmonicEgressive};
+            let result = unmark_all_differences(&[p, t, k]).unwrap();
+            assert_eq!(result.phonet, Consonant {vocal_folds: Voiceless, place: UnmarkedPlace, manner: Plosive, airstream: PulmonicEgressive});
+            assert_eq!(result.to_string(), "place varied: Bilabial, Alveolar, Velar");
+        }
+
+        #[test]
+        fn expand_unmarked_test_within_inventory()
+        {
+            let stops      = Consonant {vocal_folds: UnmarkedVocalFolds, place: UnmarkedPlace, manner: Plosive, airstream: PulmonicEgressive};
+            let g          = Consonant {vocal_folds: Voiced, place: Velar, manner: Plosive, airstream: PulmonicEgressive};
+            let pharyngeal = Consonant {vocal_folds: Voiced, place: Pharyngeal, manner: Plosive, airstream: PulmonicEgressive};
+            assert_eq!(expand_unmarked(stops).count(), generate_from_unmarked(stops).len());
+            assert_eq!(expand_unmarked(stops).excluding_impossible().within(&[g, pharyngeal]).collect::<Vec<Phonet>>(), vec![g]);
+        }
+
+        #[test]
+        fn unmark_all_differences_test_empty()
         {
-            Consonant{vocal_folds: Voiced         , place: Pharyngeal , manner: Plosive           , airstream: PulmonicEgressive} => true,
-            Consonant{vocal_folds: VoicedAspirated, place: Pharyngeal , manner: Plosive           , airstream: PulmonicEgressive} => true,
-            Consonant{vocal_folds: Voiceless      , place: Glottal    , manner: Plosive           , airstream: PulmonicEgressive} => false,  // [ʔ] is not impossible.
-            Consonant{vocal_folds: _              , place: Glottal    , manner: Fricative         , airstream: PulmonicEgressive} => false,  // [h] and [ɦ] are not impossible.
-            Consonant{vocal_folds: _              , place: Glottal    , manner: _                 , airstream: PulmonicEgressive} => true,   // all other pulmonary egressive glottal consonants are impossible..
-            Consonant{vocal_folds: _              , place: Pharyngeal , manner: Nasal             , airstream: PulmonicEgressive} => true,
-            Consonant{vocal_folds: _              , place: Pharyngeal , manner: LateralFricative  , airstream: PulmonicEgressive} => true,
-            Consonant{vocal_folds: _              , place: Pharyngeal , manner: LateralApproximant, airstream: PulmonicEgressive} => true,
-            Consonant{vocal_folds: _              , place: Velar      , manner: Trill             , airstream: PulmonicEgressive} => true,
-            Consonant{vocal_folds: _              , place: Velar      , manner: TapOrFlap         , airstream: PulmonicEgressive} => true,
-            Consonant{vocal_folds: _              , place: Bilabial   , manner: LateralFricative  , airstream: PulmonicEgressive} => true,
-            Consonant{vocal_folds: _              , place: Bilabial   , manner: LateralApproximant, airstream: PulmonicEgressive} => true,
-            Consonant{vocal_folds: _              , place: LabioDental, manner: LateralFricative  , airstream: PulmonicEgressive} => true,
-            Consonant{vocal_folds: _              , place: LabioDental, manner: LateralApproximant, airstream: PulmonicEgressive} => true,
-            _ => false, // Everything else is assumed to be possible.
+            assert_eq!(unmark_all_differences(&[]), None);
         }
     }
 }
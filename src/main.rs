@@ -1,11 +1,23 @@
-mod grapheme;
-
-use crate::grapheme::international_phonetic_alphabet::international_phonetic_alphabet::
-    {voiced_transcription, devoiced_transcription};
+#![allow(clippy::module_inception)]
+
+use phon_mod::grapheme::international_phonetic_alphabet::international_phonetic_alphabet::
+    {voiced_transcription, devoiced_transcription, analyze_transcription};
+use phon_mod::feature_diff::feature_diff::{diff_phonets, render_diff};
+use phon_mod::grapheme::transcription::transcription::{parse_transcription, construct_segments_transcription};
+use phon_mod::transformation::transformation::{parse_pipeline, PhonetTransformation};
+use phon_mod::segment::segment::{Segment, aspirated_segment, deaspirated_segment, preaspirated_segment,
+                                 geminated_segment, degeminated_segment};
 mod english_us_text;
 use crate::english_us_text::english_us_text::{USER_INPUT_VIEW_ENGLISH_PHONEME_INVENTORY,
                                               USER_INPUT_MAKE_A_PHONEME_VOICED,
                                               USER_INPUT_MAKE_A_PHONEME_UNVOICED,
+                                              USER_INPUT_COMPARE_TWO_PHONEMES,
+                                              USER_INPUT_APPLY_TRANSFORMATIONS,
+                                              USER_INPUT_ASPIRATE,
+                                              USER_INPUT_DEASPIRATE,
+                                              USER_INPUT_PREASPIRATE,
+                                              USER_INPUT_GEMINATE,
+                                              USER_INPUT_DEGEMINATE,
                                               FAILED_TO_READ_USER_INPUT,
                                               MENU,
                                               THE_USER_SELECTED,
@@ -14,6 +26,15 @@ use crate::english_us_text::english_us_text::{USER_INPUT_VIEW_ENGLISH_PHONEME_IN
                                               VIEW_ENGLISH_PHONEME_INVENTORY_NOT_IMP,
                                               PHONEME_TO_DEVOICE_MESSAGE,
                                               PHONEME_TO_VOICE_MESSAGE,
+                                              FIRST_PHONEME_TO_COMPARE_MESSAGE,
+                                              SECOND_PHONEME_TO_COMPARE_MESSAGE,
+                                              TRANSFORMATIONS_TO_APPLY_MESSAGE,
+                                              TRANSCRIPTION_TO_TRANSFORM_MESSAGE,
+                                              TRANSCRIPTION_TO_ASPIRATE_MESSAGE,
+                                              TRANSCRIPTION_TO_DEASPIRATE_MESSAGE,
+                                              TRANSCRIPTION_TO_PREASPIRATE_MESSAGE,
+                                              TRANSCRIPTION_TO_GEMINATE_MESSAGE,
+                                              TRANSCRIPTION_TO_DEGEMINATE_MESSAGE,
                                               PLEASE_READ_README_MESSAGE,
                                               PROMPT};
 use std::io;
@@ -49,6 +70,34 @@ fn main()
     {
         prompt_for_phoneme_to_devoice()
     }
+    else if selection == USER_INPUT_COMPARE_TWO_PHONEMES
+    {
+        prompt_for_phonemes_to_compare();
+    }
+    else if selection == USER_INPUT_APPLY_TRANSFORMATIONS
+    {
+        prompt_for_transformations_to_apply();
+    }
+    else if selection == USER_INPUT_ASPIRATE
+    {
+        prompt_for_segments_to_change(TRANSCRIPTION_TO_ASPIRATE_MESSAGE, aspirated_segment);
+    }
+    else if selection == USER_INPUT_DEASPIRATE
+    {
+        prompt_for_segments_to_change(TRANSCRIPTION_TO_DEASPIRATE_MESSAGE, deaspirated_segment);
+    }
+    else if selection == USER_INPUT_PREASPIRATE
+    {
+        prompt_for_segments_to_change(TRANSCRIPTION_TO_PREASPIRATE_MESSAGE, preaspirated_segment);
+    }
+    else if selection == USER_INPUT_GEMINATE
+    {
+        prompt_for_segments_to_change(TRANSCRIPTION_TO_GEMINATE_MESSAGE, geminated_segment);
+    }
+    else if selection == USER_INPUT_DEGEMINATE
+    {
+        prompt_for_segments_to_change(TRANSCRIPTION_TO_DEGEMINATE_MESSAGE, degeminated_segment);
+    }
     else
     {
        println!("{}", USER_SELECTION_NOT_HANDLED);
@@ -90,6 +139,70 @@ fn prompt_for_phoneme_to_devoice()
     println!("{}", devoiced_transcription(phoneme));
 }
 
+fn prompt_for_phonemes_to_compare()
+{
+    println!("{}", FIRST_PHONEME_TO_COMPARE_MESSAGE);
+    print_prompt();
+    let mut first = String::new();
+
+    io::stdin().read_line(&mut first)
+        .expect(FAILED_TO_READ_USER_INPUT);
+
+    println!("{}", SECOND_PHONEME_TO_COMPARE_MESSAGE);
+    print_prompt();
+    let mut second = String::new();
+
+    io::stdin().read_line(&mut second)
+        .expect(FAILED_TO_READ_USER_INPUT);
+
+    let diff = diff_phonets(analyze_transcription(first.trim().to_string()),
+                            analyze_transcription(second.trim().to_string()));
+    println!("{}", render_diff(&diff));
+}
+
+fn prompt_for_transformations_to_apply()
+{
+    println!("{}", TRANSFORMATIONS_TO_APPLY_MESSAGE);
+    print_prompt();
+    let mut transformations = String::new();
+
+    io::stdin().read_line(&mut transformations)
+        .expect(FAILED_TO_READ_USER_INPUT);
+
+    let pipeline = match parse_pipeline(transformations.trim())
+    {
+        Ok(pipeline) => pipeline,
+        Err(unknown) =>
+        {
+            println!("{}", unknown);
+            return;
+        },
+    };
+
+    println!("{}", TRANSCRIPTION_TO_TRANSFORM_MESSAGE);
+    print_prompt();
+    let mut transcription = String::new();
+
+    io::stdin().read_line(&mut transcription)
+        .expect(FAILED_TO_READ_USER_INPUT);
+
+    let segments = parse_transcription(transcription.trim());
+    println!("{}", construct_segments_transcription(&pipeline.apply_to_segments(&segments)));
+}
+
+fn prompt_for_segments_to_change(message: &str, change: fn(Segment) -> Segment)
+{
+    println!("{}", message);
+    print_prompt();
+    let mut transcription = String::new();
+
+    io::stdin().read_line(&mut transcription)
+        .expect(FAILED_TO_READ_USER_INPUT);
+
+    let changed: Vec<Segment> = parse_transcription(transcription.trim()).into_iter().map(change).collect();
+    println!("{}", construct_segments_transcription(&changed));
+}
+
 
 fn welcome()
 {
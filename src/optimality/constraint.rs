@@ -0,0 +1,198 @@
+pub mod constraint
+{
+    use std::fmt;
+    use crate::segment::segment::Segment;
+    use crate::grapheme::transcription::transcription::construct_segments_transcription;
+    use crate::rule::features::features::{BinaryFeature, FeatureSpecification, binary_feature_value, matches_features};
+
+    /// An output form, with the input segment that each of its
+    /// segments stands for (its correspondence with the input).
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub struct Candidate
+    {
+        pub output: Vec<Segment>,
+
+        // For each input segment, the output segment it became,
+        // or None if it was deleted.
+        pub correspondence: Vec<Option<usize>>
+    }
+
+    impl Candidate
+    {
+        /// The faithful candidate: the input itself.
+        pub fn faithful(input: &[Segment]) -> Candidate
+        {
+            Candidate {output: input.to_vec(), correspondence: (0..input.len()).map(Some).collect()}
+        }
+
+        /// A candidate given only as an output form. The correspondence
+        /// is the one with the fewest deletions, insertions and changes.
+        pub fn aligned(input: &[Segment], output: &[Segment]) -> Candidate
+        {
+            let (n, m) = (input.len(), output.len());
+            let mut cost = vec![vec![0; m + 1]; n + 1];
+            for (i, row) in cost.iter_mut().enumerate()
+            {
+                row[0] = i;
+            }
+            cost[0] = (0..=m).collect();
+            for i in 1..=n
+            {
+                for j in 1..=m
+                {
+                    let changed = if input[i - 1].phonet == output[j - 1].phonet { 0 } else { 1 };
+                    cost[i][j] = (cost[i - 1][j - 1] + changed).min(cost[i - 1][j] + 1).min(cost[i][j - 1] + 1);
+                }
+            }
+
+            let mut correspondence: Vec<Option<usize>> = vec![None; n];
+            let (mut i, mut j) = (n, m);
+            while i > 0
+            {
+                let changed = if j > 0 && input[i - 1].phonet == output[j - 1].phonet { 0 } else { 1 };
+                if j > 0 && cost[i][j] == cost[i - 1][j - 1] + changed
+                {
+                    correspondence[i - 1] = Some(j - 1);
+                    i -= 1;
+                    j -= 1;
+                }
+                else if cost[i][j] == cost[i - 1][j] + 1
+                {
+                    i -= 1;
+                }
+                else
+                {
+                    j -= 1;
+                }
+            }
+            Candidate {output: output.to_vec(), correspondence}
+        }
+    }
+
+    impl fmt::Display for Candidate
+    {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            write!(f, "{}", construct_segments_transcription(&self.output))
+        }
+    }
+
+
+    /// An OT constraint. Markedness constraints look at the output
+    /// only, faithfulness constraints compare it with the input.
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub enum Constraint
+    {
+        // *[F]: no segment with these features.
+        Banned(Vec<FeatureSpecification>),
+
+        // *[F]#: no such segment at the end of the word.
+        BannedFinal(Vec<FeatureSpecification>),
+
+        // *[F][G]: no segment with the first features
+        // right before one with the second.
+        BannedSequence(Vec<FeatureSpecification>, Vec<FeatureSpecification>),
+
+        // Every input segment has an output segment (no deletion).
+        Max,
+
+        // Every output segment has an input segment (no insertion).
+        Dep,
+
+        // Corresponding segments agree in this feature.
+        Ident(BinaryFeature)
+    }
+
+    fn bundle(specifications: &[FeatureSpecification]) -> String
+    {
+        format!("[{}]", specifications.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(" "))
+    }
+
+    impl fmt::Display for Constraint
+    {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            match self
+            {
+                Constraint::Banned(features)              => write!(f, "*{}", bundle(features)),
+                Constraint::BannedFinal(features)         => write!(f, "*{}#", bundle(features)),
+                Constraint::BannedSequence(first, second) => write!(f, "*{}{}", bundle(first), bundle(second)),
+                Constraint::Max                           => write!(f, "MAX"),
+                Constraint::Dep                           => write!(f, "DEP"),
+                Constraint::Ident(feature)                => write!(f, "IDENT({})", feature),
+            }
+        }
+    }
+
+    impl Constraint
+    {
+        /// Whether the constraint compares the output with the input.
+        pub fn is_faithfulness(&self) -> bool
+        {
+            matches!(self, Constraint::Max | Constraint::Dep | Constraint::Ident(_))
+        }
+
+        /// How many times a candidate violates the constraint.
+        pub fn violations(&self, input: &[Segment], candidate: &Candidate) -> usize
+        {
+            let output = &candidate.output;
+            let has = |segment: &Segment, features: &[FeatureSpecification]| matches_features(segment.phonet, features);
+            match self
+            {
+                Constraint::Banned(features) =>
+                  output.iter().filter(|x| has(x, features)).count(),
+                Constraint::BannedFinal(features) =>
+                  output.last().filter(|x| has(x, features)).map_or(0, |_| 1),
+                Constraint::BannedSequence(first, second) =>
+                  output.windows(2).filter(|pair| has(&pair[0], first) && has(&pair[1], second)).count(),
+                Constraint::Max =>
+                  candidate.correspondence.iter().filter(|x| x.is_none()).count(),
+                Constraint::Dep =>
+                  (0..output.len()).filter(|j| !candidate.correspondence.contains(&Some(*j))).count(),
+                Constraint::Ident(feature) =>
+                  candidate.correspondence.iter().enumerate()
+                           .filter_map(|(i, j)| j.map(|j| (input[i], output[j])))
+                           .filter(|(a, b)| binary_feature_value(a.phonet, *feature) != binary_feature_value(b.phonet, *feature))
+                           .count(),
+            }
+        }
+    }
+
+
+    #[cfg(test)]
+    mod constraint_tests
+    {
+        use super::*;
+        use crate::grapheme::transcription::transcription::parse_transcription;
+        use crate::rule::features::features::BinaryFeature::*;
+
+        fn spec(feature: BinaryFeature, value: bool) -> FeatureSpecification
+        {
+            FeatureSpecification {feature, value}
+        }
+
+        #[test]
+        fn violations_test()
+        {
+            let input = parse_transcription("bad");
+            let final_devoicing = Candidate::aligned(&input, &parse_transcription("bat"));
+            let voiced_obstruent = [spec(Voice, true), spec(Sonorant, false)];
+            assert_eq!(Constraint::Banned(voiced_obstruent.to_vec()).violations(&input, &final_devoicing), 1);
+            assert_eq!(Constraint::BannedFinal(voiced_obstruent.to_vec()).violations(&input, &final_devoicing), 0);
+            assert_eq!(Constraint::Ident(Voice).violations(&input, &final_devoicing), 1);
+            assert_eq!(Constraint::Max.violations(&input, &final_devoicing), 0);
+            assert_eq!(Constraint::Ident(Voice).to_string(), "IDENT(voice)");
+        }
+
+        #[test]
+        fn aligned_test_deletion_and_insertion()
+        {
+            let input = parse_transcription("bad");
+            let deleted = Candidate::aligned(&input, &parse_transcription("ba"));
+            assert_eq!(deleted.correspondence, [Some(0), Some(1), None]);
+            let inserted = Candidate::aligned(&input, &parse_transcription("badə"));
+            assert_eq!(Constraint::Dep.violations(&input, &inserted), 1);
+            assert_eq!(Constraint::Max.violations(&input, &inserted), 0);
+        }
+    }
+}
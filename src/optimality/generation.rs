@@ -0,0 +1,131 @@
+pub mod generation
+{
+    use crate::lib::impossible;
+    use crate::segment::segment::Segment;
+    use crate::transformation::transformation::{PhonetTransformation, Voice, Devoice, Spirantize};
+    use crate::grapheme::international_phonetic_alphabet::international_phonetic_alphabet::can_transcribe;
+    use crate::process::reduction::reduction::SCHWA;
+    use crate::optimality::constraint::constraint::Candidate;
+
+    /// What the candidate generator may do to the input.
+    /// Real GEN is unlimited; this one makes every candidate that is
+    /// at most `max_operations` of these operations away from the input.
+    pub struct GenerationOptions
+    {
+        // Changes that can be made to one segment.
+        pub transformations: Vec<Box<dyn PhonetTransformation>>,
+
+        // Segments that can be inserted anywhere.
+        pub epenthetic: Vec<Segment>,
+
+        // Whether segments can be deleted.
+        pub deletion: bool,
+
+        pub max_operations: usize
+    }
+
+    impl Default for GenerationOptions
+    {
+        /// Voicing, devoicing and spirantization, deletion,
+        /// and insertion of ə, one operation at a time.
+        fn default() -> GenerationOptions
+        {
+            GenerationOptions { transformations: vec![Box::new(Voice), Box::new(Devoice), Box::new(Spirantize)]
+                              , epenthetic: vec![Segment::new(SCHWA)]
+                              , deletion: true
+                              , max_operations: 1
+                              }
+        }
+    }
+
+    // The candidates that one more operation makes of a candidate.
+    fn one_operation_away(candidate: &Candidate, options: &GenerationOptions) -> Vec<Candidate>
+    {
+        let mut result: Vec<Candidate> = Vec::new();
+        let output = &candidate.output;
+        for (j, segment) in output.iter().enumerate()
+        {
+            for transformation in options.transformations.iter()
+            {
+                let phonet = transformation.apply(segment.phonet);
+                if phonet != segment.phonet && !impossible(phonet) && can_transcribe(phonet)
+                {
+                    let mut changed = output.clone();
+                    changed[j] = Segment {phonet, ..*segment};
+                    result.push(Candidate {output: changed, ..candidate.clone()});
+                }
+            }
+            if options.deletion
+            {
+                let mut shorter = output.clone();
+                shorter.remove(j);
+                let correspondence = candidate.correspondence.iter().map(|x| match x
+                {
+                    Some(k) if *k == j => None,
+                    Some(k) if *k > j  => Some(k - 1),
+                    other              => *other,
+                }).collect();
+                result.push(Candidate {output: shorter, correspondence});
+            }
+        }
+        for inserted in options.epenthetic.iter()
+        {
+            for j in 0..=output.len()
+            {
+                let mut longer = output.clone();
+                longer.insert(j, *inserted);
+                let correspondence = candidate.correspondence.iter().map(|x| x.map(|k| if k >= j { k + 1 } else { k })).collect();
+                result.push(Candidate {output: longer, correspondence});
+            }
+        }
+        result
+    }
+
+    /// A limited GEN: the faithful candidate, then every candidate
+    /// made by up to `max_operations` operations, each output form
+    /// only once (with the fewest operations that make it).
+    pub fn generate_candidates(input: &[Segment], options: &GenerationOptions) -> Vec<Candidate>
+    {
+        let mut candidates: Vec<Candidate> = vec![Candidate::faithful(input)];
+        let mut frontier: Vec<Candidate> = candidates.clone();
+        for _ in 0..options.max_operations
+        {
+            let mut next: Vec<Candidate> = Vec::new();
+            for candidate in frontier.iter()
+            {
+                for new in one_operation_away(candidate, options)
+                {
+                    if !candidates.iter().any(|x| x.output == new.output)
+                    {
+                        candidates.push(new.clone());
+                        next.push(new);
+                    }
+                }
+            }
+            frontier = next;
+        }
+        candidates
+    }
+
+
+    #[cfg(test)]
+    mod generation_tests
+    {
+        use super::*;
+        use crate::grapheme::transcription::transcription::{parse_transcription, construct_segments_transcription};
+
+        #[test]
+        fn generate_candidates_test()
+        {
+            let candidates = generate_candidates(&parse_transcription("bad"), &GenerationOptions::default());
+            let outputs: Vec<String> = candidates.iter().map(|x| construct_segments_transcription(&x.output)).collect();
+            assert_eq!(outputs[0], "bad");
+            for expected in ["bat", "pad", "ba", "ad", "badə", "əbad", "βad"]
+            {
+                assert!(outputs.contains(&String::from(expected)), "{} is missing", expected);
+            }
+            let deleted = candidates.iter().find(|x| construct_segments_transcription(&x.output) == "ba").unwrap();
+            assert_eq!(deleted.correspondence, [Some(0), Some(1), None]);
+        }
+    }
+}
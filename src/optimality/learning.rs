@@ -0,0 +1,292 @@
+pub mod learning
+{
+    use std::fmt;
+    use crate::segment::segment::Segment;
+    use crate::optimality::constraint::constraint::{Candidate, Constraint};
+    use crate::optimality::tableau::tableau::Tableau;
+    use crate::table::table::text_table;
+
+    /// A winner that the grammar should choose over a loser,
+    /// with the violations of each, one number per constraint.
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub struct WinnerLoserPair
+    {
+        pub winner: String,
+        pub loser: String,
+        pub winner_violations: Vec<usize>,
+        pub loser_violations: Vec<usize>
+    }
+
+    impl WinnerLoserPair
+    {
+        /// A pair of candidates for the same input, with their
+        /// violations counted for the constraints.
+        pub fn from_candidates(input: &[Segment], winner: &Candidate, loser: &Candidate, constraints: &[Constraint]) -> WinnerLoserPair
+        {
+            WinnerLoserPair { winner: winner.to_string()
+                            , loser: loser.to_string()
+                            , winner_violations: constraints.iter().map(|x| x.violations(input, winner)).collect()
+                            , loser_violations: constraints.iter().map(|x| x.violations(input, loser)).collect()
+                            }
+        }
+
+        /// The pairs of the intended winner of a tableau with each other candidate.
+        /// The constraints are those of the tableau, in its order.
+        pub fn from_tableau(tableau: &Tableau, winner: usize) -> Vec<WinnerLoserPair>
+        {
+            (0..tableau.candidates.len()).filter(|i| *i != winner).map(|i| WinnerLoserPair
+            { winner: tableau.candidates[winner].to_string()
+            , loser: tableau.candidates[i].to_string()
+            , winner_violations: tableau.violations[winner].clone()
+            , loser_violations: tableau.violations[i].clone()
+            }).collect()
+        }
+
+        /// Which of the two a constraint prefers.
+        pub fn preference(&self, constraint: usize) -> Preference
+        {
+            let (winner, loser) = (self.winner_violations[constraint], self.loser_violations[constraint]);
+            if winner < loser { Preference::Winner } else if winner > loser { Preference::Loser } else { Preference::Neither }
+        }
+    }
+
+    impl fmt::Display for WinnerLoserPair
+    {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            write!(f, "{} ≻ {}", self.winner, self.loser)
+        }
+    }
+
+    /// What a constraint says about a winner-loser pair,
+    /// written W, L or nothing in a comparative tableau.
+    #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+    pub enum Preference
+    {
+        Winner,
+        Loser,
+        Neither
+    }
+
+    impl fmt::Display for Preference
+    {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            match self
+            {
+                Preference::Winner  => write!(f, "W"),
+                Preference::Loser   => write!(f, "L"),
+                Preference::Neither => write!(f, ""),
+            }
+        }
+    }
+
+
+    /// A ranking as strata: the constraints of a stratum are not
+    /// ranked with each other, and each stratum outranks the next.
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub struct Strata(pub Vec<Vec<Constraint>>);
+
+    impl Strata
+    {
+        /// Every constraint, from the highest stratum down.
+        pub fn ranking(&self) -> Vec<Constraint>
+        {
+            self.0.iter().flatten().cloned().collect()
+        }
+    }
+
+    impl fmt::Display for Strata
+    {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            let strata: Vec<String> = self.0.iter().map(|stratum|
+              format!("{{{}}}", stratum.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(", "))).collect();
+            write!(f, "{}", strata.join(" ≫ "))
+        }
+    }
+
+    /// The pairs that no ranking of the constraints can explain:
+    /// every constraint that prefers a winner over its loser
+    /// also prefers a loser somewhere else, or none prefers it.
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub struct InconsistentPairs(pub Vec<WinnerLoserPair>);
+
+    impl fmt::Display for InconsistentPairs
+    {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            write!(f, "no ranking is consistent with: {}", self.0.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(", "))
+        }
+    }
+
+    // The constraints (of those left) that prefer no loser of the pairs left.
+    fn rankable(left: &[usize], pairs: &[&WinnerLoserPair]) -> Vec<usize>
+    {
+        left.iter().copied().filter(|c| pairs.iter().all(|pair| pair.preference(*c) != Preference::Loser)).collect()
+    }
+
+    fn learn(constraints: &[Constraint], pairs: &[WinnerLoserPair], biased: bool) -> Result<Strata, InconsistentPairs>
+    {
+        let mut left: Vec<usize> = (0..constraints.len()).collect();
+        let mut unexplained: Vec<&WinnerLoserPair> = pairs.iter().collect();
+        let mut strata: Vec<Vec<usize>> = Vec::new();
+        while !left.is_empty()
+        {
+            let mut stratum = rankable(&left, &unexplained);
+            if stratum.is_empty()
+            {
+                return Err(InconsistentPairs(unexplained.into_iter().cloned().collect()));
+            }
+            if biased
+            {
+                stratum = biased_stratum(constraints, &left, &unexplained, stratum);
+            }
+            unexplained.retain(|pair| !stratum.iter().any(|c| pair.preference(*c) == Preference::Winner));
+            left.retain(|c| !stratum.contains(c));
+            strata.push(stratum);
+        }
+        if !unexplained.is_empty()
+        {
+            return Err(InconsistentPairs(unexplained.into_iter().cloned().collect()));
+        }
+        Ok(Strata(strata.iter().map(|stratum| stratum.iter().map(|c| constraints[*c].clone()).collect()).collect()))
+    }
+
+    // Biased Constraint Demotion: rank markedness constraints as high as
+    // possible. If none can be ranked, rank only one faithfulness
+    // constraint that prefers some winner: the one that frees the most
+    // markedness constraints for the next stratum.
+    fn biased_stratum(constraints: &[Constraint], left: &[usize], unexplained: &[&WinnerLoserPair], stratum: Vec<usize>) -> Vec<usize>
+    {
+        let markedness: Vec<usize> = stratum.iter().copied().filter(|c| !constraints[*c].is_faithfulness()).collect();
+        if !markedness.is_empty()
+        {
+            return markedness;
+        }
+        let active: Vec<usize> = stratum.iter().copied()
+          .filter(|c| unexplained.iter().any(|pair| pair.preference(*c) == Preference::Winner)).collect();
+        let markedness_left: Vec<usize> = left.iter().copied().filter(|c| !constraints[*c].is_faithfulness()).collect();
+        if active.is_empty() || markedness_left.is_empty()
+        {
+            return stratum;
+        }
+        let freed = |faithfulness: usize|
+        {
+            let still: Vec<&WinnerLoserPair> = unexplained.iter().copied().filter(|pair| pair.preference(faithfulness) != Preference::Winner).collect();
+            rankable(&markedness_left, &still).len()
+        };
+        let best = active.iter().copied().max_by_key(|c| (freed(*c), std::cmp::Reverse(*c))).unwrap_or(active[0]);
+        vec![best]
+    }
+
+    /// Recursive Constraint Demotion (Tesar and Smolensky): finds the
+    /// highest ranking in which every winner beats its loser, by ranking
+    /// at each step every constraint that prefers no loser left.
+    pub fn recursive_constraint_demotion(constraints: &[Constraint], pairs: &[WinnerLoserPair]) -> Result<Strata, InconsistentPairs>
+    {
+        learn(constraints, pairs, false)
+    }
+
+    /// Biased Constraint Demotion (Prince and Tesar): like Recursive
+    /// Constraint Demotion, but keeps faithfulness constraints as low
+    /// as the data allow, which gives the most restrictive grammar.
+    pub fn biased_constraint_demotion(constraints: &[Constraint], pairs: &[WinnerLoserPair]) -> Result<Strata, InconsistentPairs>
+    {
+        learn(constraints, pairs, true)
+    }
+
+
+    /// A comparative tableau: one row per winner-loser pair,
+    /// with W or L for each constraint that prefers one of them.
+    /// `constraints` are in the order of the columns, and the
+    /// violations of the pairs are in that same order.
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub struct ComparativeTableau
+    {
+        pub constraints: Vec<Constraint>,
+        pub pairs: Vec<WinnerLoserPair>
+    }
+
+    /// A comparative tableau with the columns in the order of learned
+    /// strata. `constraints` is the order of the violations in the pairs.
+    pub fn comparative_tableau(constraints: &[Constraint], pairs: &[WinnerLoserPair], strata: &Strata) -> ComparativeTableau
+    {
+        let ranking = strata.ranking();
+        let order: Vec<usize> = ranking.iter().filter_map(|x| constraints.iter().position(|c| c == x)).collect();
+        let reordered = |violations: &[usize]| order.iter().map(|i| violations[*i]).collect::<Vec<usize>>();
+        ComparativeTableau
+        { constraints: order.iter().map(|i| constraints[*i].clone()).collect()
+        , pairs: pairs.iter().map(|pair| WinnerLoserPair { winner_violations: reordered(&pair.winner_violations)
+                                                         , loser_violations: reordered(&pair.loser_violations)
+                                                         , ..pair.clone()
+                                                         }).collect()
+        }
+    }
+
+    impl fmt::Display for ComparativeTableau
+    {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            let mut rows: Vec<Vec<String>> = Vec::new();
+            let mut header = vec![String::from("winner ≻ loser")];
+            header.extend(self.constraints.iter().map(|x| x.to_string()));
+            rows.push(header);
+            for pair in self.pairs.iter()
+            {
+                let mut row = vec![pair.to_string()];
+                row.extend((0..self.constraints.len()).map(|c| pair.preference(c).to_string()));
+                rows.push(row);
+            }
+            write!(f, "{}", text_table(&rows))
+        }
+    }
+
+
+    #[cfg(test)]
+    mod learning_tests
+    {
+        use super::*;
+        use crate::grapheme::transcription::transcription::parse_transcription;
+        use crate::rule::features::features::{FeatureSpecification, BinaryFeature::*};
+
+        fn final_devoicing_data() -> (Vec<Constraint>, Vec<WinnerLoserPair>)
+        {
+            let voiced_obstruent = vec![FeatureSpecification {feature: Voice, value: true}, FeatureSpecification {feature: Sonorant, value: false}];
+            let constraints = vec![Constraint::Ident(Voice), Constraint::Max, Constraint::Dep, Constraint::BannedFinal(voiced_obstruent)];
+            let input = parse_transcription("bad");
+            let winner = Candidate::aligned(&input, &parse_transcription("bat"));
+            let pairs = ["bad", "ba", "badə"].iter()
+              .map(|loser| WinnerLoserPair::from_candidates(&input, &winner, &Candidate::aligned(&input, &parse_transcription(loser)), &constraints))
+              .collect();
+            (constraints, pairs)
+        }
+
+        #[test]
+        fn recursive_constraint_demotion_test()
+        {
+            let (constraints, pairs) = final_devoicing_data();
+            let strata = recursive_constraint_demotion(&constraints, &pairs).unwrap();
+            assert_eq!(strata.to_string(), "{MAX, DEP, *[+voice -son]#} ≫ {IDENT(voice)}");
+            let tableau = comparative_tableau(&constraints, &pairs, &strata);
+            assert_eq!(tableau.to_string().lines().nth(1), Some("bat ≻ bad      |     |     | W               | L"));
+        }
+
+        #[test]
+        fn biased_constraint_demotion_test()
+        {
+            let (constraints, pairs) = final_devoicing_data();
+            let strata = biased_constraint_demotion(&constraints, &pairs).unwrap();
+            assert_eq!(strata.to_string(), "{*[+voice -son]#} ≫ {MAX, DEP} ≫ {IDENT(voice)}");
+        }
+
+        #[test]
+        fn recursive_constraint_demotion_test_inconsistent()
+        {
+            let constraints = vec![Constraint::Max, Constraint::Dep];
+            let pair = WinnerLoserPair {winner: String::from("a"), loser: String::from("b"), winner_violations: vec![1, 0], loser_violations: vec![0, 0]};
+            let error = recursive_constraint_demotion(&constraints, &[pair]).unwrap_err();
+            assert_eq!(error.to_string(), "no ranking is consistent with: a ≻ b");
+        }
+    }
+}
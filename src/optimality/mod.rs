@@ -0,0 +1,4 @@
+pub mod constraint;
+pub mod generation;
+pub mod learning;
+pub mod tableau;
@@ -0,0 +1,174 @@
+pub mod tableau
+{
+    use std::fmt;
+    use crate::segment::segment::Segment;
+    use crate::grapheme::transcription::transcription::construct_segments_transcription;
+    use crate::optimality::constraint::constraint::{Candidate, Constraint};
+    use crate::table::table::text_table;
+
+    /// The candidates for one input, evaluated under a ranking
+    /// (the constraints from highest to lowest).
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub struct Tableau
+    {
+        pub input: Vec<Segment>,
+        pub ranking: Vec<Constraint>,
+        pub candidates: Vec<Candidate>,
+
+        // For each candidate, its violations of each constraint.
+        pub violations: Vec<Vec<usize>>
+    }
+
+    /// Counts the violations of every candidate.
+    pub fn evaluate(input: &[Segment], candidates: &[Candidate], ranking: &[Constraint]) -> Tableau
+    {
+        let violations = candidates.iter().map(|candidate| ranking.iter().map(|x| x.violations(input, candidate)).collect()).collect();
+        Tableau {input: input.to_vec(), ranking: ranking.to_vec(), candidates: candidates.to_vec(), violations}
+    }
+
+    impl Tableau
+    {
+        /// The indexes of the optimal candidates: those whose violations,
+        /// read from the highest constraint down, are fewest.
+        /// There is more than one only if they tie on every constraint.
+        pub fn optimal(&self) -> Vec<usize>
+        {
+            match self.violations.iter().min()
+            {
+                Some(best) => (0..self.candidates.len()).filter(|i| self.violations[*i] == *best).collect(),
+                None       => Vec::new(),
+            }
+        }
+
+        /// The first optimal candidate.
+        pub fn winner(&self) -> Option<&Candidate>
+        {
+            self.optimal().first().map(|i| &self.candidates[*i])
+        }
+
+        /// The constraint (by position in the ranking) where a candidate
+        /// loses to the winner, or None for an optimal candidate.
+        pub fn fatal_violation(&self, candidate: usize) -> Option<usize>
+        {
+            let best = &self.violations[*self.optimal().first()?];
+            (0..self.ranking.len()).find(|i| self.violations[candidate][*i] > best[*i])
+        }
+
+        // The marks for one cell: a * per violation, and a ! after
+        // the one that loses to the winner.
+        fn marks(&self, candidate: usize, constraint: usize) -> String
+        {
+            let count = self.violations[candidate][constraint];
+            if self.fatal_violation(candidate) == Some(constraint)
+            {
+                let best = self.violations[self.optimal()[0]][constraint];
+                format!("{}!{}", "*".repeat(best + 1), "*".repeat(count - best - 1))
+            }
+            else
+            {
+                "*".repeat(count)
+            }
+        }
+
+        // Whether a cell no longer matters: the candidate has
+        // already lost on a higher constraint.
+        fn shaded(&self, candidate: usize, constraint: usize) -> bool
+        {
+            matches!(self.fatal_violation(candidate), Some(fatal) if constraint > fatal)
+        }
+
+        /// The tableau as an HTML table, with the winner pointed at
+        /// and the cells after a fatal violation in class "shaded".
+        pub fn to_html(&self) -> String
+        {
+            let escape = |text: String| text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+            let optimal = self.optimal();
+            let mut html = String::from("<table class=\"tableau\">\n<tr><th></th>");
+            html.push_str(&format!("<th>/{}/</th>", construct_segments_transcription(&self.input)));
+            for constraint in self.ranking.iter()
+            {
+                html.push_str(&format!("<th>{}</th>", escape(constraint.to_string())));
+            }
+            html.push_str("</tr>\n");
+            for (i, candidate) in self.candidates.iter().enumerate()
+            {
+                let pointer = if optimal.contains(&i) { "☞" } else { "" };
+                html.push_str(&format!("<tr><td>{}</td><td>{}</td>", pointer, candidate));
+                for j in 0..self.ranking.len()
+                {
+                    let class = if self.shaded(i, j) { " class=\"shaded\"" } else { "" };
+                    html.push_str(&format!("<td{}>{}</td>", class, self.marks(i, j)));
+                }
+                html.push_str("</tr>\n");
+            }
+            html.push_str("</table>");
+            html
+        }
+    }
+
+    /// The tableau in plain text, one candidate per line.
+    impl fmt::Display for Tableau
+    {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            let optimal = self.optimal();
+            let mut rows: Vec<Vec<String>> = Vec::new();
+            let mut header = vec![format!("  /{}/", construct_segments_transcription(&self.input))];
+            header.extend(self.ranking.iter().map(|x| x.to_string()));
+            rows.push(header);
+            for (i, candidate) in self.candidates.iter().enumerate()
+            {
+                let pointer = if optimal.contains(&i) { "☞" } else { " " };
+                let mut row = vec![format!("{} {}", pointer, candidate)];
+                row.extend((0..self.ranking.len()).map(|j| self.marks(i, j)));
+                rows.push(row);
+            }
+            write!(f, "{}", text_table(&rows))
+        }
+    }
+
+
+    #[cfg(test)]
+    mod tableau_tests
+    {
+        use super::*;
+        use crate::grapheme::transcription::transcription::parse_transcription;
+        use crate::rule::features::features::{FeatureSpecification, BinaryFeature, BinaryFeature::*};
+        use crate::optimality::generation::generation::{generate_candidates, GenerationOptions};
+
+        fn spec(feature: BinaryFeature, value: bool) -> FeatureSpecification
+        {
+            FeatureSpecification {feature, value}
+        }
+
+        fn final_devoicing() -> Vec<Constraint>
+        {
+            vec![Constraint::BannedFinal(vec![spec(Voice, true), spec(Sonorant, false)]), Constraint::Max, Constraint::Dep, Constraint::Ident(Voice)]
+        }
+
+        #[test]
+        fn evaluate_test_final_devoicing()
+        {
+            let input = parse_transcription("bad");
+            let candidates = generate_candidates(&input, &GenerationOptions::default());
+            let tableau = evaluate(&input, &candidates, &final_devoicing());
+            assert_eq!(tableau.winner().unwrap().to_string(), "bat");
+            assert_eq!(tableau.fatal_violation(0), Some(0));
+        }
+
+        #[test]
+        fn tableau_test_text_and_html()
+        {
+            let input = parse_transcription("bad");
+            let candidates: Vec<Candidate> = ["bad", "bat", "ba"].iter().map(|x| Candidate::aligned(&input, &parse_transcription(x))).collect();
+            let tableau = evaluate(&input, &candidates, &final_devoicing());
+            assert_eq!(tableau.to_string(), "  /bad/ | *[+voice -son]# | MAX | DEP | IDENT(voice)\n  \
+                                               bad   | *!              |     |     |\n\
+                                             ☞ bat   |                 |     |     | *\n  \
+                                               ba    |                 | *!  |     |");
+            let html = tableau.to_html();
+            assert!(html.contains("<tr><td>☞</td><td>bat</td><td></td><td></td><td></td><td>*</td></tr>"));
+            assert!(html.contains("<td class=\"shaded\"></td>"));
+        }
+    }
+}
@@ -0,0 +1,412 @@
+pub mod plausibility
+{
+    use std::fmt;
+    use crate::lib::
+        {Phonet, Phonet::*,
+         VocalFolds, VocalFolds::*,
+         Place, Place::*,
+         Manner::*,
+         Airstream::*,
+         Height::*,
+         Backness::*,
+         Rounding::*,
+         expand_unmarked};
+    use crate::segment::segment::{Segment, SecondaryArticulation::*};
+    use Plausibility::*;
+
+    // See: https://www.internationalphoneticassociation.org/sites/default/files/IPA_Kiel_2015.pdf
+    // for the shaded (judged impossible) cells of the chart.
+    // The grades other than the impossible ones are rough,
+    // they are meant to flag sounds worth double checking in an inventory,
+    // not to replace a survey of the world's languages.
+
+    /// How likely it is to find a sound in a language,
+    /// from least likely to most likely.
+    #[derive(Eq, PartialEq, Debug, Clone, Copy, PartialOrd, Ord)]
+    pub enum Plausibility
+    {
+        Impossible,       // Cannot be articulated.
+        ChartImpossible,  // Shaded on the IPA chart, judged impossible.
+        Unattested,       // Could be articulated but no language is known to use it.
+        Rare,             // Used by some languages.
+        Common            // Used by many languages.
+    }
+
+    impl fmt::Display for Plausibility
+    {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            let name = match self
+            {
+                Impossible      => "impossible",
+                ChartImpossible => "judged impossible on the IPA chart",
+                Unattested      => "unattested",
+                Rare            => "rare",
+                Common          => "common",
+            };
+            write!(f, "{}", name)
+        }
+    }
+
+    /// A plausibility together with the reason it was given.
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub struct Verdict
+    {
+        pub plausibility: Plausibility,
+        pub reason: String
+    }
+
+    impl fmt::Display for Verdict
+    {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            write!(f, "{}: {}", self.plausibility, self.reason)
+        }
+    }
+
+    fn verdict(plausibility: Plausibility, reason: &str) -> Verdict
+    {
+        Verdict {plausibility, reason: String::from(reason)}
+    }
+
+
+    /// Judges how plausible a sound is, for any airstream and for vowels.
+    /// A phonet with unmarked features gets the best verdict
+    /// of any phonet it could stand for.
+    pub fn plausibility(phonet: Phonet) -> Verdict
+    {
+        if is_underspecified(phonet)
+        {
+            return underspecified_plausibility(phonet);
+        }
+
+        match phonet
+        {
+            Consonant {airstream: PulmonicEgressive, ..} => pulmonic_plausibility(phonet),
+            Consonant {airstream: Click, ..}             => click_plausibility(phonet),
+            Consonant {airstream: Implosive, ..}         => implosive_plausibility(phonet),
+            Consonant {..}                               => verdict(Unattested, "the airstream mechanism is not known"),
+            Vowel {..}                                   => vowel_plausibility(phonet),
+        }
+    }
+
+    /// Judges how plausible a segment is, taking its secondary
+    /// articulation into account. The verdict is the worse
+    /// of the verdict for the phonet and the verdict for
+    /// adding the secondary articulation to it.
+    pub fn segment_plausibility(segment: Segment) -> Verdict
+    {
+        let main = plausibility(segment.phonet);
+        let secondary = secondary_articulation_plausibility(segment);
+        if secondary.plausibility < main.plausibility
+        {
+            secondary
+        }
+        else
+        {
+            main
+        }
+    }
+
+
+    // Clicks and implosives are analyzed without a manner
+    // (and clicks without voicing), so those are not counted
+    // as underspecified.
+    fn is_underspecified(phonet: Phonet) -> bool
+    {
+        match phonet
+        {
+            Consonant {airstream: UnmarkedAirstream, ..} => true,
+            Consonant {place: UnmarkedPlace, ..}         => true,
+            Consonant {vocal_folds: UnmarkedVocalFolds, airstream: PulmonicEgressive, ..} => true,
+            Consonant {vocal_folds: UnmarkedVocalFolds, airstream: Implosive, ..}         => true,
+            Consonant {manner: UnmarkedManner, airstream: PulmonicEgressive, ..}          => true,
+            Consonant {..} => false,
+            Vowel {height: UnmarkedHeight, ..}           => true,
+            Vowel {backness: UnmarkedBackness, ..}       => true,
+            Vowel {rounding: UnmarkedRounding, ..}       => true,
+            Vowel {vocal_folds: UnmarkedVocalFolds, ..}  => true,
+            Vowel {..} => false,
+        }
+    }
+
+    fn underspecified_plausibility(phonet: Phonet) -> Verdict
+    {
+        let best = expand_unmarked(phonet)
+                     .filter(|p| !is_underspecified(*p))
+                     .map(plausibility)
+                     .max_by_key(|v| v.plausibility);
+        match best
+        {
+            Some(v) => Verdict {plausibility: v.plausibility, reason: format!("at best, {}", v.reason)},
+            None    => verdict(Unattested, "no fully specified sound matches"),
+        }
+    }
+
+
+    fn pulmonic_plausibility(phonet: Phonet) -> Verdict
+    {
+        let (voice, place, manner) = match phonet
+        {
+            Consonant {vocal_folds, place, manner, ..} => (vocal_folds, place, manner),
+            Vowel {..} => return vowel_plausibility(phonet),
+        };
+
+        let is_lateral = matches!(manner, LateralFricative | LateralApproximant | LateralFlap | Lateral);
+        let is_sonorant = matches!(manner, Nasal | Trill | TapOrFlap | Approximant | LateralApproximant | LateralFlap | Lateral);
+
+        match (voice, place, manner)
+        {
+            // Judged impossible (or impossible) on the IPA chart:
+            (_                          , Glottal    , Nasal)      => verdict(Impossible, "a nasal needs air to reach the nose, which a closed glottis prevents"),
+            (_                          , Pharyngeal , Nasal)      => verdict(Impossible, "a closure in the pharynx also blocks the nasal cavity"),
+            (_                          , Epiglottal , Nasal)      => verdict(Impossible, "a closure in the pharynx also blocks the nasal cavity"),
+            (_                          , Glottal    , _) if is_lateral => verdict(Impossible, "a lateral needs the tongue, which plays no part at the glottis"),
+            (_                          , Pharyngeal , _) if is_lateral => verdict(Impossible, "the root of the tongue cannot leave a channel along its sides"),
+            (_                          , Epiglottal , _) if is_lateral => verdict(Impossible, "the root of the tongue cannot leave a channel along its sides"),
+            (_                          , Bilabial   , _) if is_lateral => verdict(Impossible, "a lateral needs the tongue, which plays no part in a labial articulation"),
+            (_                          , LabioDental, _) if is_lateral => verdict(Impossible, "a lateral needs the tongue, which plays no part in a labial articulation"),
+            (Voiceless                  , Glottal    , Plosive)    => verdict(Common    , "the glottal stop [ʔ] is found in many languages"),
+            (_                          , Glottal    , Plosive)    => verdict(Impossible, "the vocal folds cannot vibrate or be spread while they are closed"),
+            (Voiceless                  , Glottal    , Fricative)  => verdict(Common    , "[h] is found in many languages"),
+            (_                          , Glottal    , Fricative)  => verdict(Rare      , "[ɦ] and other glottal fricatives are found in some languages"),
+            (_                          , Glottal    , _)          => verdict(ChartImpossible, "only stops and fricatives are made at the glottis on the IPA chart"),
+            (Voiced                     , Pharyngeal , Plosive)    => verdict(ChartImpossible, "a voiced pharyngeal plosive is shaded on the IPA chart"),
+            (VoicedAspirated            , Pharyngeal , Plosive)    => verdict(ChartImpossible, "a voiced pharyngeal plosive is shaded on the IPA chart"),
+            (CreakyVoiced               , Pharyngeal , Plosive)    => verdict(ChartImpossible, "a voiced pharyngeal plosive is shaded on the IPA chart"),
+            (_                          , Velar      , Trill)      => verdict(ChartImpossible, "a velar trill is shaded on the IPA chart"),
+            (_                          , Velar      , TapOrFlap)  => verdict(ChartImpossible, "a velar tap is shaded on the IPA chart"),
+
+            // Unattested:
+            (_                          , _          , Lateral)    => verdict(Unattested, "a lateral without a manner is only used for clicks"),
+            (VoicelessAspirated         , _          , _) if is_sonorant => verdict(Unattested, "aspiration is not known to contrast on sonorants"),
+            (_                          , LabioDental, Trill)      => verdict(Unattested, "no language is known to have a labiodental trill"),
+            (_                          , Palatal    , Trill)      => verdict(Unattested, "no language is known to have a palatal trill"),
+            (_                          , Palatal    , TapOrFlap)  => verdict(Unattested, "no language is known to have a palatal tap"),
+            (_                          , Pharyngeal , Affricate)  => verdict(Unattested, "no language is known to have a pharyngeal affricate"),
+            (_                          , LabialPalatal, Approximant) => verdict(Rare   , "[ɥ] is found in some languages"),
+            (_                          , LabialPalatal, _)        => verdict(Unattested, "labial-palatals are only known as approximants"),
+            (_                          , AlveoloPalatal, Trill)   => verdict(Unattested, "no language is known to have an alveolo-palatal trill"),
+            (_                          , AlveoloPalatal, _) if is_lateral => verdict(Unattested, "no language is known to contrast alveolo-palatal laterals"),
+
+            // Common:
+            (Voiceless                  , Bilabial   , Plosive)    => verdict(Common, "[p] is found in many languages"),
+            (Voiced                     , Bilabial   , Plosive)    => verdict(Common, "[b] is found in many languages"),
+            (Voiceless                  , Dental     , Plosive)    => verdict(Common, "dental [t̪] is found in many languages"),
+            (Voiced                     , Dental     , Plosive)    => verdict(Common, "dental [d̪] is found in many languages"),
+            (Voiceless                  , Alveolar   , Plosive)    => verdict(Common, "[t] is found in many languages"),
+            (Voiced                     , Alveolar   , Plosive)    => verdict(Common, "[d] is found in many languages"),
+            (Voiceless                  , Velar      , Plosive)    => verdict(Common, "[k] is found in many languages"),
+            (Voiced                     , Velar      , Plosive)    => verdict(Common, "[g] is found in many languages"),
+            (VoicelessAspirated         , Bilabial   , Plosive)    => verdict(Common, "aspirated [pʰ] is found in many languages"),
+            (VoicelessAspirated         , Alveolar   , Plosive)    => verdict(Common, "aspirated [tʰ] is found in many languages"),
+            (VoicelessAspirated         , Velar      , Plosive)    => verdict(Common, "aspirated [kʰ] is found in many languages"),
+            (Voiced                     , Bilabial   , Nasal)      => verdict(Common, "[m] is found in many languages"),
+            (Voiced                     , Dental     , Nasal)      => verdict(Common, "dental [n̪] is found in many languages"),
+            (Voiced                     , Alveolar   , Nasal)      => verdict(Common, "[n] is found in many languages"),
+            (Voiced                     , Palatal    , Nasal)      => verdict(Common, "[ɲ] is found in many languages"),
+            (Voiced                     , Velar      , Nasal)      => verdict(Common, "[ŋ] is found in many languages"),
+            (Voiceless                  , LabioDental, Fricative)  => verdict(Common, "[f] is found in many languages"),
+            (Voiced                     , LabioDental, Fricative)  => verdict(Common, "[v] is found in many languages"),
+            (Voiceless                  , Alveolar   , Fricative)  => verdict(Common, "[s] is found in many languages"),
+            (Voiced                     , Alveolar   , Fricative)  => verdict(Common, "[z] is found in many languages"),
+            (Voiceless                  , PostAlveolar, Fricative) => verdict(Common, "[ʃ] is found in many languages"),
+            (Voiced                     , PostAlveolar, Fricative) => verdict(Common, "[ʒ] is found in many languages"),
+            (Voiceless                  , Velar      , Fricative)  => verdict(Common, "[x] is found in many languages"),
+            (Voiceless                  , PostAlveolar, Affricate) => verdict(Common, "[t͡ʃ] is found in many languages"),
+            (Voiced                     , PostAlveolar, Affricate) => verdict(Common, "[d͡ʒ] is found in many languages"),
+            (Voiceless                  , Alveolar   , Affricate)  => verdict(Common, "[t͜s] is found in many languages"),
+            (Voiced                     , Alveolar   , Trill)      => verdict(Common, "[r] is found in many languages"),
+            (Voiced                     , Alveolar   , TapOrFlap)  => verdict(Common, "[ɾ] is found in many languages"),
+            (Voiced                     , Palatal    , Approximant) => verdict(Common, "[j] is found in many languages"),
+            (Voiced                     , LabialVelar, Approximant) => verdict(Common, "[w] is found in many languages"),
+            (Voiced                     , Alveolar   , LateralApproximant) => verdict(Common, "[l] is found in many languages"),
+            (Voiced                     , Dental     , LateralApproximant) => verdict(Common, "dental [l̪] is found in many languages"),
+
+            // Everything else that can be articulated:
+            (VoicedAspirated            , _          , _)          => verdict(Rare, "breathy voiced consonants are found in some languages"),
+            (CreakyVoiced               , _          , _)          => verdict(Rare, "creaky voiced consonants are found in some languages"),
+            (Voiceless                  , _          , _) if is_sonorant => verdict(Rare, "voiceless sonorants are found in some languages"),
+            (VoicelessAspirated         , _          , Fricative)  => verdict(Rare, "aspirated fricatives are found in some languages"),
+            _                                                      => verdict(Rare, "this sound is found in some languages, but is not among the most common"),
+        }
+    }
+
+
+    fn click_plausibility(phonet: Phonet) -> Verdict
+    {
+        let (place, manner) = match phonet
+        {
+            Consonant {place, manner, ..} => (place, manner),
+            Vowel {..} => return vowel_plausibility(phonet),
+        };
+
+        match (place, manner)
+        {
+            (Velar      , _) => verdict(Impossible, "a click needs a closure at the velum or uvula behind its front closure"),
+            (Uvular     , _) => verdict(Impossible, "a click needs a closure at the velum or uvula behind its front closure"),
+            (Pharyngeal , _) => verdict(Impossible, "a click needs a closure at the velum or uvula behind its front closure"),
+            (Epiglottal , _) => verdict(Impossible, "a click needs a closure at the velum or uvula behind its front closure"),
+            (Glottal    , _) => verdict(Impossible, "a click needs a closure at the velum or uvula behind its front closure"),
+            (LabialVelar, _) => verdict(Impossible, "a click needs a closure at the velum or uvula behind its front closure"),
+            (LabioDental, _) => verdict(Unattested, "no language is known to have a labiodental click"),
+            (LabialPalatal, _) => verdict(Unattested, "no language is known to have a labial-palatal click"),
+            (_, Trill)       => verdict(Unattested, "clicks are stops, they are not known to be trilled"),
+            (_, TapOrFlap)   => verdict(Unattested, "clicks are stops, they are not known to be tapped"),
+            (_, Approximant) => verdict(Unattested, "clicks are stops, they are not known as approximants"),
+            (_, LateralFlap) => verdict(Unattested, "clicks are stops, they are not known to be tapped"),
+            (Alveolar    , Lateral)            => verdict(Rare, "the lateral click [ǁ] is found in a few languages of southern and eastern Africa"),
+            (Alveolar    , LateralFricative)   => verdict(Rare, "the lateral click [ǁ] is found in a few languages of southern and eastern Africa"),
+            (Alveolar    , LateralApproximant) => verdict(Rare, "the lateral click [ǁ] is found in a few languages of southern and eastern Africa"),
+            (_, Lateral)            => verdict(Unattested, "lateral clicks are only known at the alveolar ridge"),
+            (_, LateralFricative)   => verdict(Unattested, "lateral clicks are only known at the alveolar ridge"),
+            (_, LateralApproximant) => verdict(Unattested, "lateral clicks are only known at the alveolar ridge"),
+            (_, Nasal)       => verdict(Rare, "nasal clicks are found in a few languages of southern and eastern Africa"),
+            _                => verdict(Rare, "clicks are found in a few languages of southern and eastern Africa"),
+        }
+    }
+
+
+    fn implosive_plausibility(phonet: Phonet) -> Verdict
+    {
+        let (voice, place, manner) = match phonet
+        {
+            Consonant {vocal_folds, place, manner, ..} => (vocal_folds, place, manner),
+            Vowel {..} => return vowel_plausibility(phonet),
+        };
+
+        match (voice, place, manner)
+        {
+            (_, Glottal    , _) => verdict(Impossible, "an implosive lowers the larynx behind an oral closure, a glottal closure leaves nothing to lower"),
+            (_, Pharyngeal , _) => verdict(ChartImpossible, "there is no room to lower the larynx behind a pharyngeal closure"),
+            (_, Epiglottal , _) => verdict(ChartImpossible, "there is no room to lower the larynx behind an epiglottal closure"),
+            (_, _          , Nasal) => verdict(Impossible, "air coming in through the nose would stop the pressure from dropping"),
+            (_, _          , UnmarkedManner) => implosive_stop_plausibility(voice, place),
+            (_, _          , Plosive)        => implosive_stop_plausibility(voice, place),
+            _ => verdict(Unattested, "implosives are only known as stops"),
+        }
+    }
+
+    fn implosive_stop_plausibility(voice: VocalFolds, place: Place) -> Verdict
+    {
+        match (voice, place)
+        {
+            (VoicedAspirated, _) => verdict(Unattested, "no language is known to have a breathy voiced implosive"),
+            (_, LabioDental)     => verdict(Unattested, "no language is known to have a labiodental implosive"),
+            (_, LabialPalatal)   => verdict(Unattested, "no language is known to have a labial-palatal implosive"),
+            (Voiceless, _)          => verdict(Rare, "voiceless implosives are found in a handful of languages"),
+            (VoicelessAspirated, _) => verdict(Rare, "voiceless implosives are found in a handful of languages"),
+            _ => verdict(Rare, "implosives are found in some languages, mostly in Africa and Southeast Asia"),
+        }
+    }
+
+
+    fn vowel_plausibility(phonet: Phonet) -> Verdict
+    {
+        let (height, backness, rounding, voice) = match phonet
+        {
+            Vowel {height, backness, rounding, vocal_folds} => (height, backness, rounding, vocal_folds),
+            Consonant {..} => return pulmonic_plausibility(phonet),
+        };
+
+        match (height, backness, rounding, voice)
+        {
+            (_        , _      , _        , VoicelessAspirated) => verdict(Unattested, "aspiration is part of the release of a consonant, not a vowel quality"),
+            (_        , _      , _        , Voiceless)          => verdict(Rare, "voiceless vowels are found in some languages, usually as allophones"),
+            (_        , _      , _        , VoicedAspirated)    => verdict(Rare, "breathy voiced vowels are found in some languages"),
+            (_        , _      , _        , CreakyVoiced)       => verdict(Rare, "creaky voiced vowels are found in some languages"),
+            (Close    , Front  , Unrounded, _) => verdict(Common, "[i] is found in almost every language"),
+            (Close    , Back   , Rounded  , _) => verdict(Common, "[u] is found in almost every language"),
+            (Close    , Central, Unrounded, _) => verdict(Common, "[ɨ] is found in many languages"),
+            (NearClose, Front  , Unrounded, _) => verdict(Common, "[ɪ] is found in many languages"),
+            (NearClose, Back   , Rounded  , _) => verdict(Common, "[ʊ] is found in many languages"),
+            (CloseMid , Front  , Unrounded, _) => verdict(Common, "[e] is found in many languages"),
+            (CloseMid , Back   , Rounded  , _) => verdict(Common, "[o] is found in many languages"),
+            (Mid      , Central, Unrounded, _) => verdict(Common, "[ə] is found in many languages"),
+            (OpenMid  , Front  , Unrounded, _) => verdict(Common, "[ɛ] is found in many languages"),
+            (OpenMid  , Back   , Rounded  , _) => verdict(Common, "[ɔ] is found in many languages"),
+            (NearOpen , Front  , Unrounded, _) => verdict(Common, "[æ] is found in many languages"),
+            (Open     , Front  , Unrounded, _) => verdict(Common, "[a] is found in almost every language"),
+            (Open     , Back   , Unrounded, _) => verdict(Common, "[ɑ] is found in many languages"),
+            (Open     , Front  , Rounded  , _) => verdict(Rare, "[ɶ] is very rarely contrastive"),
+            (_        , Front  , Rounded  , _) => verdict(Rare, "front rounded vowels are found in some languages"),
+            (_        , Back   , Unrounded, _) => verdict(Rare, "back unrounded vowels are found in some languages"),
+            (_        , Central, Rounded  , _) => verdict(Rare, "central rounded vowels are found in some languages"),
+            _                                  => verdict(Rare, "this vowel is found in some languages, but is not among the most common"),
+        }
+    }
+
+
+    fn secondary_articulation_plausibility(segment: Segment) -> Verdict
+    {
+        match (segment.phonet, segment.secondary)
+        {
+            (_, Plain) => verdict(Common, "there is no secondary articulation"),
+
+            (Vowel {..}, Labialized)     => verdict(Unattested, "a vowel is labialized by rounding it instead"),
+            (Vowel {..}, Palatalized)    => verdict(Unattested, "a vowel is palatalized by fronting it instead"),
+            (Vowel {..}, Velarized)      => verdict(Unattested, "a vowel is velarized by backing it instead"),
+            (Vowel {..}, Pharyngealized) => verdict(Rare, "pharyngealized vowels are found in some languages"),
+
+            (Consonant {place: LabialVelar  , ..}, Labialized)  => verdict(Unattested, "the sound is already labial"),
+            (Consonant {place: LabialPalatal, ..}, Labialized)  => verdict(Unattested, "the sound is already labial"),
+            (Consonant {place: Bilabial     , ..}, Labialized)  => verdict(Rare, "labialized labials are found in a few languages"),
+            (Consonant {place: LabioDental  , ..}, Labialized)  => verdict(Rare, "labialized labials are found in a few languages"),
+            (Consonant {place: Velar        , ..}, Labialized)  => verdict(Common, "labialized velars are found in many languages"),
+            (Consonant {place: Uvular       , ..}, Labialized)  => verdict(Common, "labialized uvulars are found in many languages"),
+            (Consonant {..}                      , Labialized)  => verdict(Rare, "labialization is found on these sounds in some languages"),
+
+            (Consonant {place: Palatal       , ..}, Palatalized) => verdict(Unattested, "the sound is already palatal"),
+            (Consonant {place: AlveoloPalatal, ..}, Palatalized) => verdict(Unattested, "the sound is already palatal"),
+            (Consonant {place: LabialPalatal , ..}, Palatalized) => verdict(Unattested, "the sound is already palatal"),
+            (Consonant {place: Pharyngeal    , ..}, Palatalized) => verdict(Unattested, "the tongue root cannot be drawn back while the body is raised to the palate"),
+            (Consonant {place: Epiglottal    , ..}, Palatalized) => verdict(Unattested, "the tongue root cannot be drawn back while the body is raised to the palate"),
+            (Consonant {place: Bilabial      , ..}, Palatalized) => verdict(Common, "palatalized labials are found in many languages"),
+            (Consonant {place: Alveolar      , ..}, Palatalized) => verdict(Common, "palatalized alveolars are found in many languages"),
+            (Consonant {place: Dental        , ..}, Palatalized) => verdict(Common, "palatalized dentals are found in many languages"),
+            (Consonant {..}                       , Palatalized) => verdict(Rare, "palatalization is found on these sounds in some languages"),
+
+            (Consonant {place: Velar      , ..}, Velarized) => verdict(Unattested, "the sound is already velar"),
+            (Consonant {place: LabialVelar, ..}, Velarized) => verdict(Unattested, "the sound is already velar"),
+            (Consonant {place: Uvular     , ..}, Velarized) => verdict(Impossible, "the back of the tongue cannot be at the velum and the uvula at once"),
+            (Consonant {place: Pharyngeal , ..}, Velarized) => verdict(Impossible, "the back of the tongue cannot be at the velum and the pharynx at once"),
+            (Consonant {place: Epiglottal , ..}, Velarized) => verdict(Impossible, "the back of the tongue cannot be at the velum and the pharynx at once"),
+            (Consonant {place: Alveolar, manner: LateralApproximant, ..}, Velarized) => verdict(Common, "the dark l [ɫ] is found in many languages"),
+            (Consonant {..}                    , Velarized) => verdict(Rare, "velarization is found on these sounds in some languages"),
+
+            (Consonant {place: Pharyngeal, ..}, Pharyngealized) => verdict(Unattested, "the sound is already pharyngeal"),
+            (Consonant {place: Epiglottal, ..}, Pharyngealized) => verdict(Unattested, "the sound is already pharyngeal"),
+            (Consonant {place: Glottal   , ..}, Pharyngealized) => verdict(Unattested, "no language is known to pharyngealize a glottal sound"),
+            (Consonant {..}                   , Pharyngealized) => verdict(Rare, "pharyngealized consonants (such as the emphatics of Arabic) are found in some languages"),
+        }
+    }
+
+
+    #[cfg(test)]
+    mod plausibility_tests
+    {
+        use super::*;
+
+        #[test]
+        fn plausibility_test_glottal_nasal_is_impossible()
+        {
+            let phonet = Consonant {vocal_folds: Voiced, place: Glottal, manner: Nasal, airstream: PulmonicEgressive};
+            assert_eq!(plausibility(phonet).plausibility, Impossible);
+        }
+
+        #[test]
+        fn plausibility_test_velar_click_is_impossible()
+        {
+            let phonet = Consonant {vocal_folds: UnmarkedVocalFolds, place: Velar, manner: UnmarkedManner, airstream: Click};
+            assert_eq!(plausibility(phonet).plausibility, Impossible);
+        }
+
+        #[test]
+        fn segment_plausibility_test_dark_l()
+        {
+            let l = Consonant {vocal_folds: Voiced, place: Alveolar, manner: LateralApproximant, airstream: PulmonicEgressive};
+            let dark_l = Segment {secondary: Velarized, ..Segment::new(l)};
+            assert_eq!(segment_plausibility(dark_l).plausibility, Common);
+        }
+    }
+}
@@ -0,0 +1,194 @@
+pub mod assimilation
+{
+    use std::fmt;
+    use crate::lib::{Feature, feature_value, with_feature_value, impossible, Phonet::*, Manner::*};
+    use crate::segment::segment::Segment;
+    use crate::grapheme::transcription::transcription::construct_segment_transcription;
+    use crate::grapheme::international_phonetic_alphabet::international_phonetic_alphabet::can_transcribe;
+
+    /// Which way a feature spreads: leftward from a trigger to the
+    /// segments before it (regressive), or rightward to the ones
+    /// after it (progressive).
+    #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+    pub enum Direction
+    {
+        Leftward,
+        Rightward
+    }
+
+    /// A feature that spreads from the segments that trigger it
+    /// to the segments that undergo it.
+    #[derive(Debug, Clone, Copy)]
+    pub struct Spreading
+    {
+        pub feature: Feature,
+        pub direction: Direction,
+
+        // How far from the trigger a segment can be and still undergo
+        // the spreading. A window of 1 reaches only the next segment.
+        pub window: usize,
+
+        pub trigger: fn(Segment) -> bool,
+        pub target: fn(Segment) -> bool
+    }
+
+    fn is_nasal(segment: Segment) -> bool
+    {
+        matches!(segment.phonet, Consonant {manner: Nasal, ..})
+    }
+
+    fn is_obstruent(segment: Segment) -> bool
+    {
+        matches!(segment.phonet, Consonant {manner: Plosive, ..}
+                               | Consonant {manner: Affricate, ..}
+                               | Consonant {manner: Fricative, ..})
+    }
+
+    impl Spreading
+    {
+        /// A nasal takes the place of the obstruent right after it:
+        /// n+p → mp, n+k → ŋk, n+f → ɱf.
+        pub fn nasal_place_assimilation() -> Spreading
+        {
+            Spreading { feature: Feature::Place
+                      , direction: Direction::Leftward
+                      , window: 1
+                      , trigger: is_obstruent
+                      , target: is_nasal
+                      }
+        }
+
+        /// An obstruent takes the voicing of the obstruent right after it,
+        /// as in Russian: t+d → dd, z+k → sk.
+        pub fn regressive_voicing_assimilation() -> Spreading
+        {
+            Spreading { feature: Feature::VocalFolds
+                      , direction: Direction::Leftward
+                      , window: 1
+                      , trigger: is_obstruent
+                      , target: is_obstruent
+                      }
+        }
+    }
+
+
+    /// One segment that the spreading changed.
+    #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+    pub struct SpreadChange
+    {
+        pub index: usize,
+        pub before: Segment,
+        pub after: Segment,
+
+        // Where the segment the feature came from is.
+        pub trigger_index: usize
+    }
+
+    impl fmt::Display for SpreadChange
+    {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            write!(f, "{}: {} → {} (from {})", self.index,
+                   construct_segment_transcription(self.before),
+                   construct_segment_transcription(self.after),
+                   self.trigger_index)
+        }
+    }
+
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub struct SpreadResult
+    {
+        pub segments: Vec<Segment>,
+        pub changes: Vec<SpreadChange>
+    }
+
+
+    // The nearest trigger within the window of a segment, looking
+    // the way the feature comes from.
+    fn nearest_trigger(segments: &[Segment], index: usize, spreading: &Spreading) -> Option<usize>
+    {
+        (1..=spreading.window)
+          .filter_map(|distance| match spreading.direction
+                                 {
+                                     Direction::Leftward  => index.checked_add(distance).filter(|i| *i < segments.len()),
+                                     Direction::Rightward => index.checked_sub(distance),
+                                 })
+          .find(|i| (spreading.trigger)(segments[*i]))
+    }
+
+    /// Spreads a feature through a sequence of segments.
+    /// Every trigger spreads the value it has in the input, so the
+    /// spreading does not feed itself. A segment within reach of more
+    /// than one trigger takes the value of the nearest. Changes that
+    /// would give an impossible phonet, or one with no symbol, are left out.
+    pub fn spread(segments: &[Segment], spreading: &Spreading) -> SpreadResult
+    {
+        let mut result: Vec<Segment> = segments.to_vec();
+        let mut changes: Vec<SpreadChange> = Vec::new();
+        for (index, before) in segments.iter().enumerate()
+        {
+            if !(spreading.target)(*before)
+            {
+                continue;
+            }
+            let trigger_index = match nearest_trigger(segments, index, spreading)
+            {
+                Some(x) => x,
+                None    => continue,
+            };
+            let value = match feature_value(segments[trigger_index].phonet, spreading.feature)
+            {
+                Some(x) => x,
+                None    => continue,
+            };
+            match with_feature_value(before.phonet, value)
+            {
+                Some(phonet) if phonet != before.phonet && !impossible(phonet) && can_transcribe(phonet) =>
+                {
+                    let after = Segment {phonet, ..*before};
+                    result[index] = after;
+                    changes.push(SpreadChange {index, before: *before, after, trigger_index});
+                },
+                _ => (),
+            }
+        }
+        SpreadResult {segments: result, changes}
+    }
+
+
+    #[cfg(test)]
+    mod assimilation_tests
+    {
+        use super::*;
+        use crate::grapheme::transcription::transcription::{parse_transcription, construct_segments_transcription};
+
+        #[test]
+        fn spread_test_nasal_place_assimilation()
+        {
+            let segments = parse_transcription("anpankanka");
+            let result = spread(&segments, &Spreading::nasal_place_assimilation());
+            assert_eq!(construct_segments_transcription(&result.segments), "ampaŋkaŋka");
+            assert_eq!(result.changes.len(), 3);
+            assert_eq!(result.changes[0].to_string(), "1: n → m (from 2)");
+        }
+
+        #[test]
+        fn spread_test_regressive_voicing()
+        {
+            let segments = parse_transcription("otdazka");
+            let result = spread(&segments, &Spreading::regressive_voicing_assimilation());
+            assert_eq!(construct_segments_transcription(&result.segments), "oddaska");
+        }
+
+        #[test]
+        fn spread_test_no_symbol_for_result()
+        {
+            // Dental and alveolo-palatal nasals have no symbol,
+            // so n stays before θ and ɕ.
+            let segments = parse_transcription("anθanɕa");
+            let result = spread(&segments, &Spreading::nasal_place_assimilation());
+            assert!(result.changes.is_empty());
+            assert_eq!(result.segments, segments);
+        }
+    }
+}
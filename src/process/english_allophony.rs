@@ -0,0 +1,272 @@
+pub mod english_allophony
+{
+    use std::fmt;
+    use crate::lib::{Phonet, Phonet::*, VocalFolds, VocalFolds::*, Place::*, Manner, Manner::*, Airstream::*, aspirated_phonet};
+    use crate::segment::segment::{Segment, Length::*, Stress::*, syllable_stress};
+    use crate::process::secondary_articulation::secondary_articulation::velarized;
+    use crate::grapheme::transcription::transcription::construct_segments_transcription;
+
+    /// One allophonic rule of English. It looks at one segment
+    /// of a word and its neighbours, and gives the segment it becomes,
+    /// or None if the rule does not apply there.
+    #[derive(Clone, Copy)]
+    pub struct AllophonyRule
+    {
+        pub name: &'static str,
+        pub description: &'static str,
+        pub apply: fn(&[Segment], usize) -> Option<Segment>
+    }
+
+    fn is_vowel(segment: Option<&Segment>) -> bool
+    {
+        matches!(segment, Some(Segment {phonet: Vowel {..}, ..}))
+    }
+
+    fn is_approximant(segment: Option<&Segment>) -> bool
+    {
+        matches!(segment, Some(Segment {phonet: Consonant {manner: Approximant, ..}, ..})
+                        | Some(Segment {phonet: Consonant {manner: LateralApproximant, ..}, ..}))
+    }
+
+    fn is_phonet(segment: Option<&Segment>, phonet: Phonet) -> bool
+    {
+        segment.map(|x| x.phonet) == Some(phonet)
+    }
+
+    fn previous(segments: &[Segment], index: usize) -> Option<&Segment>
+    {
+        if index > 0 { segments.get(index - 1) } else { None }
+    }
+
+    fn alveolar(vocal_folds: VocalFolds, manner: Manner) -> Phonet
+    {
+        Consonant {vocal_folds, place: Alveolar, manner, airstream: PulmonicEgressive}
+    }
+
+
+    // A voiceless stop at the start of a stressed syllable,
+    // before the vowel or an approximant and the vowel, and not after s.
+    fn aspiration(segments: &[Segment], index: usize) -> Option<Segment>
+    {
+        let segment = segments[index];
+        let voiceless_stop = matches!(segment.phonet, Consonant {vocal_folds: Voiceless, manner: Plosive, airstream: PulmonicEgressive, ..});
+        let next = segments.get(index + 1);
+        let in_onset = is_vowel(next) || (is_approximant(next) && is_vowel(segments.get(index + 2)));
+        let after_s = is_phonet(previous(segments, index), alveolar(Voiceless, Fricative));
+        if voiceless_stop && in_onset && !after_s && syllable_stress(segments, index) != Unstressed
+        {
+            Some(Segment {phonet: aspirated_phonet(segment.phonet), ..segment})
+        }
+        else
+        {
+            None
+        }
+    }
+
+    // A vowel before a voiced obstruent at the end of its syllable.
+    fn lengthening(segments: &[Segment], index: usize) -> Option<Segment>
+    {
+        let segment = segments[index];
+        let next = segments.get(index + 1);
+        let voiced_obstruent = matches!(next, Some(Segment {phonet: Consonant {vocal_folds: Voiced, manner: Plosive, ..}, ..})
+                                            | Some(Segment {phonet: Consonant {vocal_folds: Voiced, manner: Fricative, ..}, ..})
+                                            | Some(Segment {phonet: Consonant {vocal_folds: Voiced, manner: Affricate, ..}, ..}));
+        // The obstruent closes the syllable: it ends the word,
+        // or comes before a consonant rather than a vowel.
+        let in_coda = next.is_some() && !is_vowel(segments.get(index + 2));
+        if is_vowel(Some(&segment)) && voiced_obstruent && in_coda && segment.length == Short
+        {
+            Some(Segment {length: Long, ..segment})
+        }
+        else
+        {
+            None
+        }
+    }
+
+    // t or d after a vowel (or ɹ) and before an unstressed vowel.
+    fn flapping(segments: &[Segment], index: usize) -> Option<Segment>
+    {
+        let segment = segments[index];
+        let t_or_d = segment.phonet == alveolar(Voiceless, Plosive) || segment.phonet == alveolar(Voiced, Plosive);
+        let before = previous(segments, index);
+        let after_vowel = is_vowel(before) || is_phonet(before, alveolar(Voiced, Approximant));
+        let before_unstressed_vowel = is_vowel(segments.get(index + 1)) && syllable_stress(segments, index + 1) == Unstressed;
+        if t_or_d && after_vowel && before_unstressed_vowel
+        {
+            Some(Segment {phonet: alveolar(Voiced, TapOrFlap), ..segment})
+        }
+        else
+        {
+            None
+        }
+    }
+
+    // t after a vowel and before a nasal, as in button.
+    fn glottalization(segments: &[Segment], index: usize) -> Option<Segment>
+    {
+        let segment = segments[index];
+        let before_nasal = matches!(segments.get(index + 1), Some(Segment {phonet: Consonant {manner: Nasal, ..}, ..}));
+        if segment.phonet == alveolar(Voiceless, Plosive) && is_vowel(previous(segments, index)) && before_nasal
+        {
+            let glottal_stop = Consonant {vocal_folds: Voiceless, place: Glottal, manner: Plosive, airstream: PulmonicEgressive};
+            Some(Segment {phonet: glottal_stop, ..segment})
+        }
+        else
+        {
+            None
+        }
+    }
+
+    // l that is not before a vowel.
+    fn dark_l(segments: &[Segment], index: usize) -> Option<Segment>
+    {
+        let segment = segments[index];
+        if segment.phonet == alveolar(Voiced, LateralApproximant) && !is_vowel(segments.get(index + 1))
+        {
+            Some(velarized(segment))
+        }
+        else
+        {
+            None
+        }
+    }
+
+    /// The rules, in the order they apply.
+    pub static ENGLISH_ALLOPHONY_RULES: [AllophonyRule; 5] =
+      [ AllophonyRule { name: "aspiration"
+                      , description: "voiceless stops are aspirated at the start of a stressed syllable"
+                      , apply: aspiration
+                      }
+      , AllophonyRule { name: "vowel lengthening"
+                      , description: "vowels are lengthened before a voiced obstruent in the same syllable"
+                      , apply: lengthening
+                      }
+      , AllophonyRule { name: "flapping"
+                      , description: "t and d become ɾ after a vowel and before an unstressed vowel"
+                      , apply: flapping
+                      }
+      , AllophonyRule { name: "glottalization"
+                      , description: "t becomes ʔ after a vowel and before a nasal"
+                      , apply: glottalization
+                      }
+      , AllophonyRule { name: "dark l"
+                      , description: "l is velarized when no vowel follows it"
+                      , apply: dark_l
+                      }
+      ];
+
+
+    /// What one rule did to a word.
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub struct AllophonyStep
+    {
+        pub rule: &'static str,
+        pub description: &'static str,
+        pub before: Vec<Segment>,
+        pub after: Vec<Segment>,
+
+        // The positions of the segments that changed.
+        pub changed: Vec<usize>
+    }
+
+    impl fmt::Display for AllophonyStep
+    {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            if self.changed.is_empty()
+            {
+                write!(f, "{}: no change", self.rule)
+            }
+            else
+            {
+                write!(f, "{}: {} → {} ({})", self.rule,
+                       construct_segments_transcription(&self.before),
+                       construct_segments_transcription(&self.after),
+                       self.description)
+            }
+        }
+    }
+
+    fn apply_rule(segments: &[Segment], rule: &AllophonyRule) -> AllophonyStep
+    {
+        let mut after: Vec<Segment> = segments.to_vec();
+        let mut changed: Vec<usize> = Vec::new();
+        for index in 0..segments.len()
+        {
+            if let Some(segment) = (rule.apply)(segments, index)
+            {
+                if segment != segments[index]
+                {
+                    after[index] = segment;
+                    changed.push(index);
+                }
+            }
+        }
+        AllophonyStep {rule: rule.name, description: rule.description, before: segments.to_vec(), after, changed}
+    }
+
+    /// Applies the English allophony rules to a broad (phonemic)
+    /// transcription of one word, with its stress marks, and shows
+    /// what each rule did.
+    pub fn explain_english_allophony(segments: &[Segment]) -> Vec<AllophonyStep>
+    {
+        let mut steps: Vec<AllophonyStep> = Vec::new();
+        let mut current: Vec<Segment> = segments.to_vec();
+        for rule in ENGLISH_ALLOPHONY_RULES.iter()
+        {
+            let step = apply_rule(&current, rule);
+            current = step.after.clone();
+            steps.push(step);
+        }
+        steps
+    }
+
+    /// Turns a broad (phonemic) transcription of one English word,
+    /// with its stress marks, into a narrow (phonetic) one.
+    pub fn english_narrow_transcription(segments: &[Segment]) -> Vec<Segment>
+    {
+        match explain_english_allophony(segments).pop()
+        {
+            Some(step) => step.after,
+            None       => segments.to_vec(),
+        }
+    }
+
+
+    #[cfg(test)]
+    mod english_allophony_tests
+    {
+        use super::*;
+        use crate::grapheme::transcription::transcription::parse_transcription;
+
+        fn narrow(text: &str) -> String
+        {
+            construct_segments_transcription(&english_narrow_transcription(&parse_transcription(text)))
+        }
+
+        #[test]
+        fn english_narrow_transcription_test()
+        {
+            assert_eq!(narrow("ˈpɪl"), "ˈpʰɪɫ");
+            assert_eq!(narrow("ˈspɪl"), "ˈspɪɫ");
+            assert_eq!(narrow("ˈwɔtəɹ"), "ˈwɔɾəɹ");
+            assert_eq!(narrow("ˈbæd"), "ˈbæːd");
+            assert_eq!(narrow("ˈsodə"), "ˈsoɾə");
+            assert_eq!(narrow("ˈæbət"), "ˈæbət");
+            assert_eq!(narrow("ˈæbz"), "ˈæːbz");
+            assert_eq!(narrow("ˈbʌtn"), "ˈbʌʔn");
+            assert_eq!(narrow("əˈtæk"), "əˈtʰæk");
+        }
+
+        #[test]
+        fn explain_english_allophony_test()
+        {
+            let steps = explain_english_allophony(&parse_transcription("ˈtɛl"));
+            let explanation: Vec<String> = steps.iter().map(|step| step.to_string()).collect();
+            assert_eq!(explanation[0], "aspiration: ˈtɛl → ˈtʰɛl (voiceless stops are aspirated at the start of a stressed syllable)");
+            assert_eq!(explanation[2], "flapping: no change");
+            assert_eq!(steps[4].changed, [2]);
+        }
+    }
+}
@@ -0,0 +1,328 @@
+pub mod harmony
+{
+    use std::fmt;
+    use crate::lib::{Phonet, Phonet::*, Feature, FeatureValue, feature_value, with_feature_value, impossible,
+                     Height, Height::*, Backness, Rounding, Place::*, Manner::*};
+    use crate::segment::segment::Segment;
+    use crate::grapheme::international_phonetic_alphabet::international_phonetic_alphabet::can_transcribe;
+    use crate::process::assimilation::assimilation::{Direction, SpreadChange};
+
+    /// The feature that a harmony makes agree across a word.
+    #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+    pub enum HarmonyFeature
+    {
+        Backness,
+        Rounding,
+
+        // Advanced tongue root. The model has no such feature, so it is
+        // read from the height of a vowel: i e u o are +ATR and
+        // ɪ ɛ ʊ ɔ are −ATR. Other vowels do not take part.
+        Atr,
+
+        // The place of a consonant, as in sibilant harmony (s and ʃ).
+        Place
+    }
+
+    /// A long-distance harmony. The value of the feature is taken from
+    /// each trigger and passed on, in the direction of the harmony,
+    /// to the targets after it, across any segment in between.
+    #[derive(Debug, Clone, Copy)]
+    pub struct Harmony
+    {
+        pub feature: HarmonyFeature,
+        pub direction: Direction,
+        pub trigger: fn(Segment) -> bool,
+        pub target: fn(Segment) -> bool,
+
+        // Segments that neither undergo the harmony nor pass on
+        // their own value (such as Finnish i and e).
+        pub transparent: fn(Segment) -> bool,
+
+        // Segments that do not undergo the harmony, and pass on
+        // their own value instead, so the harmony stops at them.
+        pub opaque: fn(Segment) -> bool
+    }
+
+    fn is_vowel(segment: Segment) -> bool
+    {
+        matches!(segment.phonet, Vowel {..})
+    }
+
+    fn is_high_vowel(segment: Segment) -> bool
+    {
+        matches!(segment.phonet, Vowel {height: Close, ..} | Vowel {height: NearClose, ..})
+    }
+
+    fn is_neutral_front_vowel(segment: Segment) -> bool
+    {
+        matches!(segment.phonet, Vowel {height: Close, backness: Backness::Front, rounding: Rounding::Unrounded, ..}
+                               | Vowel {height: CloseMid, backness: Backness::Front, rounding: Rounding::Unrounded, ..})
+    }
+
+    fn is_sibilant(segment: Segment) -> bool
+    {
+        matches!(segment.phonet, Consonant {place: Alveolar, manner: Fricative, ..}
+                               | Consonant {place: Alveolar, manner: Affricate, ..}
+                               | Consonant {place: PostAlveolar, manner: Fricative, ..}
+                               | Consonant {place: PostAlveolar, manner: Affricate, ..})
+    }
+
+    fn nothing(_: Segment) -> bool
+    {
+        false
+    }
+
+    impl Harmony
+    {
+        /// Turkish backness harmony: every vowel takes the backness
+        /// of the vowel before it (ev-in → evin, kol-in → kolɯn).
+        pub fn turkish_backness() -> Harmony
+        {
+            Harmony { feature: HarmonyFeature::Backness
+                    , direction: Direction::Rightward
+                    , trigger: is_vowel
+                    , target: is_vowel
+                    , transparent: nothing
+                    , opaque: nothing
+                    }
+        }
+
+        /// Turkish rounding harmony: high vowels take the rounding
+        /// of the vowel before them (kolɯn → kolun).
+        pub fn turkish_rounding() -> Harmony
+        {
+            Harmony { target: is_high_vowel
+                    , feature: HarmonyFeature::Rounding
+                    , ..Harmony::turkish_backness()
+                    }
+        }
+
+        /// Finnish (and Hungarian) backness harmony, where the front
+        /// unrounded vowels i and e are transparent.
+        pub fn finnish_backness() -> Harmony
+        {
+            Harmony { transparent: is_neutral_front_vowel
+                    , ..Harmony::turkish_backness()
+                    }
+        }
+
+        /// Navajo sibilant harmony: every sibilant takes the place
+        /// (s or ʃ) of the last sibilant of the word.
+        pub fn navajo_sibilant() -> Harmony
+        {
+            Harmony { feature: HarmonyFeature::Place
+                    , direction: Direction::Leftward
+                    , trigger: is_sibilant
+                    , target: is_sibilant
+                    , transparent: nothing
+                    , opaque: nothing
+                    }
+        }
+    }
+
+
+    // The value of the harmonizing feature. Tongue root is a value
+    // of its own, since the model has no feature for it.
+    #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+    enum HarmonyValue
+    {
+        Feature(FeatureValue),
+        AdvancedTongueRoot(bool)
+    }
+
+    fn harmony_value(feature: HarmonyFeature, phonet: Phonet) -> Option<HarmonyValue>
+    {
+        let value = |feature: Feature| feature_value(phonet, feature).map(HarmonyValue::Feature);
+        match (feature, phonet)
+        {
+            (HarmonyFeature::Backness, Vowel {..})     => value(Feature::Backness),
+            (HarmonyFeature::Rounding, Vowel {..})     => value(Feature::Rounding),
+            (HarmonyFeature::Place, Consonant {..})    => value(Feature::Place),
+            (HarmonyFeature::Atr, Vowel {height, ..})  => match height
+            {
+                Close | CloseMid     => Some(HarmonyValue::AdvancedTongueRoot(true)),
+                NearClose | OpenMid  => Some(HarmonyValue::AdvancedTongueRoot(false)),
+                _                    => None,
+            },
+            _ => None,
+        }
+    }
+
+    // The phonet with a harmony value, or None if that leaves it as it
+    // is or gives a phonet that is impossible or has no symbol.
+    fn with_harmony_value(phonet: Phonet, value: HarmonyValue) -> Option<Phonet>
+    {
+        let changed = match (phonet, value)
+        {
+            (_, HarmonyValue::Feature(value)) => with_feature_value(phonet, value)?,
+            (Vowel {height, backness, rounding, vocal_folds}, HarmonyValue::AdvancedTongueRoot(advanced)) =>
+            {
+                let height: Height = match (height, advanced)
+                {
+                    (NearClose, true) => Close,
+                    (OpenMid, true)   => CloseMid,
+                    (Close, false)    => NearClose,
+                    (CloseMid, false) => OpenMid,
+                    _ => height,
+                };
+                Vowel {height, backness, rounding, vocal_folds}
+            },
+            _ => return None,
+        };
+        if changed == phonet || impossible(changed) || !can_transcribe(changed) { None } else { Some(changed) }
+    }
+
+
+    /// A word after a harmony, and which segments took part.
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub struct HarmonyResult
+    {
+        pub segments: Vec<Segment>,
+
+        // The segments that changed, each with the trigger its value came from.
+        pub changes: Vec<SpreadChange>
+    }
+
+    impl HarmonyResult
+    {
+        /// The indexes of the segments whose value spread to another one.
+        pub fn triggers(&self) -> Vec<usize>
+        {
+            let mut triggers: Vec<usize> = Vec::new();
+            for change in self.changes.iter()
+            {
+                if !triggers.contains(&change.trigger_index)
+                {
+                    triggers.push(change.trigger_index);
+                }
+            }
+            triggers.sort();
+            triggers
+        }
+
+        /// The indexes of the segments that underwent the harmony.
+        pub fn undergoers(&self) -> Vec<usize>
+        {
+            let mut undergoers: Vec<usize> = self.changes.iter().map(|x| x.index).collect();
+            undergoers.sort();
+            undergoers
+        }
+    }
+
+    impl fmt::Display for HarmonyResult
+    {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            let changes: Vec<String> = self.changes.iter().map(|x| x.to_string()).collect();
+            write!(f, "{}", changes.join("\n"))
+        }
+    }
+
+    /// Applies a harmony across a word. Going in the direction of the
+    /// harmony, each target takes the value of the last trigger (or
+    /// opaque segment) before it. A target that cannot take the value
+    /// (there is no such sound, or no symbol for it) stays as it is.
+    pub fn harmonize(segments: &[Segment], harmony: &Harmony) -> HarmonyResult
+    {
+        let mut result: Vec<Segment> = segments.to_vec();
+        let mut changes: Vec<SpreadChange> = Vec::new();
+        let order: Vec<usize> = match harmony.direction
+        {
+            Direction::Rightward => (0..segments.len()).collect(),
+            Direction::Leftward  => (0..segments.len()).rev().collect(),
+        };
+
+        // The value being passed on, and the segment it came from.
+        let mut current: Option<(HarmonyValue, usize)> = None;
+        for i in order
+        {
+            let segment = segments[i];
+            if (harmony.transparent)(segment)
+            {
+                continue;
+            }
+            if (harmony.opaque)(segment)
+            {
+                current = harmony_value(harmony.feature, segment.phonet).map(|value| (value, i)).or(current);
+                continue;
+            }
+            if (harmony.target)(segment)
+            {
+                if let Some((value, trigger_index)) = current
+                {
+                    if harmony_value(harmony.feature, segment.phonet) != Some(value)
+                    {
+                        if let Some(phonet) = with_harmony_value(segment.phonet, value)
+                        {
+                            result[i] = Segment {phonet, ..segment};
+                            changes.push(SpreadChange {index: i, before: segment, after: result[i], trigger_index});
+                        }
+                    }
+                    continue;
+                }
+            }
+            if (harmony.trigger)(segment)
+            {
+                current = harmony_value(harmony.feature, segment.phonet).map(|value| (value, i)).or(current);
+            }
+        }
+        changes.sort_by_key(|x| x.index);
+        HarmonyResult {segments: result, changes}
+    }
+
+
+    #[cfg(test)]
+    mod harmony_tests
+    {
+        use super::*;
+        use crate::grapheme::transcription::transcription::{parse_transcription, construct_segments_transcription};
+
+        fn apply(text: &str, harmonies: &[Harmony]) -> String
+        {
+            let segments = harmonies.iter().fold(parse_transcription(text), |acc, harmony| harmonize(&acc, harmony).segments);
+            construct_segments_transcription(&segments)
+        }
+
+        #[test]
+        fn harmonize_test_turkish_finnish_navajo()
+        {
+            let turkish = [Harmony::turkish_backness(), Harmony::turkish_rounding()];
+            assert_eq!(apply("evin", &turkish), "evin");
+            assert_eq!(apply("kolin", &turkish), "kolun");
+            assert_eq!(apply("kotina", &[Harmony::finnish_backness()]), "kotinɑ");
+            assert_eq!(apply("sitʃid", &[Harmony::navajo_sibilant()]), "ʃitʃid");
+        }
+
+        #[test]
+        fn harmonize_test_transparent_and_opaque()
+        {
+            let backness = Harmony::turkish_backness();
+            assert_eq!(apply("oie", &[backness]), "oɯɤ");
+            let is_i = |x| is_high_vowel(x) && is_neutral_front_vowel(x);
+            assert_eq!(apply("oie", &[Harmony {transparent: is_i, ..backness}]), "oiɤ");
+            let result = harmonize(&parse_transcription("oie"), &Harmony {opaque: is_i, ..backness});
+            assert_eq!(construct_segments_transcription(&result.segments), "oie");
+            assert!(result.changes.is_empty());
+        }
+
+        #[test]
+        fn harmonize_test_triggers_and_undergoers()
+        {
+            let result = harmonize(&parse_transcription("ɪpetu"), &Harmony {feature: HarmonyFeature::Atr, ..Harmony::turkish_backness()});
+            assert_eq!(construct_segments_transcription(&result.segments), "ɪpɛtʊ");
+            assert_eq!(result.triggers(), [0]);
+            assert_eq!(result.undergoers(), [2, 4]);
+            assert_eq!(result.changes[0].to_string(), "2: e → ɛ (from 0)");
+        }
+
+        #[test]
+        fn harmonize_test_low_vowel_under_atr()
+        {
+            // a has no ATR value to change, so it does not undergo the harmony.
+            let result = harmonize(&parse_transcription("ipa"), &Harmony {feature: HarmonyFeature::Atr, ..Harmony::turkish_backness()});
+            assert!(result.changes.is_empty());
+            assert!(result.undergoers().is_empty());
+            assert_eq!(construct_segments_transcription(&result.segments), "ipa");
+        }
+    }
+}
@@ -0,0 +1,307 @@
+pub mod lenition
+{
+    use crate::lib::{Phonet::*, Place, Place::*, Manner, VocalFolds::*, Airstream::*};
+    use crate::segment::segment::{Segment, Length::*};
+    use Strength::*;
+
+    /// A step on the consonant strength hierarchy,
+    /// from the strongest (a geminate) to the weakest (no segment at all).
+    #[derive(Eq, PartialEq, Debug, Clone, Copy, PartialOrd, Ord)]
+    pub enum Strength
+    {
+        Geminate, Stop, Affricate, Fricative, Approximant, Deleted
+    }
+
+    pub static STRENGTH_STATES: [Strength; 6] =
+      [Geminate, Stop, Affricate, Fricative, Approximant, Deleted];
+
+
+    /// How lenition and fortition should behave.
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub struct LenitionOptions
+    {
+        // The strengths a segment can move between, strongest first.
+        // A segment moves to the next strength on this scale,
+        // so leaving out a step makes lenition skip it.
+        pub scale: Vec<Strength>,
+
+        // Whether losing aspiration and then voicing count as steps
+        // of lenition (before the manner changes), for example p → b.
+        pub voicing: bool,
+
+        // Whether only segments that are already voiced lenite
+        // (as in Spanish, where b d g spirantize but p t k do not).
+        pub only_voiced: bool,
+
+        // Places that shift when a stop or affricate becomes
+        // a fricative or approximant, as (from, to). For example
+        // (Alveolar, Dental) makes t become θ instead of s.
+        // Fortition shifts them back.
+        pub place_shifts: Vec<(Place, Place)>
+    }
+
+    impl Default for LenitionOptions
+    {
+        /// The whole scale, with voicing, and without place shifts.
+        fn default() -> LenitionOptions
+        {
+            LenitionOptions { scale: STRENGTH_STATES.to_vec()
+                            , voicing: true
+                            , only_voiced: false
+                            , place_shifts: Vec::new()
+                            }
+        }
+    }
+
+    impl LenitionOptions
+    {
+        /// Spanish spirantization: b d g become β ð ɣ.
+        pub fn spanish_spirantization() -> LenitionOptions
+        {
+            LenitionOptions { scale: vec![Stop, Fricative]
+                            , voicing: false
+                            , only_voiced: true
+                            , place_shifts: vec![(Alveolar, Dental)]
+                            }
+        }
+
+        /// Close to the Welsh soft mutation: p t k become b d g,
+        /// and b d g become v ð ɣ.
+        pub fn celtic_lenition() -> LenitionOptions
+        {
+            LenitionOptions { scale: vec![Geminate, Stop, Fricative, Deleted]
+                            , voicing: true
+                            , only_voiced: false
+                            , place_shifts: vec![(Bilabial, LabioDental), (Alveolar, Dental)]
+                            }
+        }
+    }
+
+
+    fn manner_strength(manner: Manner) -> Option<Strength>
+    {
+        match manner
+        {
+            Manner::Plosive     => Some(Stop),
+            Manner::Affricate   => Some(Affricate),
+            Manner::Fricative   => Some(Fricative),
+            Manner::Approximant => Some(Approximant),
+            _                   => None,
+        }
+    }
+
+    fn strength_manner(strength: Strength) -> Option<Manner>
+    {
+        match strength
+        {
+            Stop        => Some(Manner::Plosive),
+            Affricate   => Some(Manner::Affricate),
+            Fricative   => Some(Manner::Fricative),
+            Approximant => Some(Manner::Approximant),
+            _           => None,
+        }
+    }
+
+    fn is_continuant(strength: Strength) -> bool
+    {
+        strength == Fricative || strength == Approximant
+    }
+
+    /// Where a segment is on the strength hierarchy.
+    /// Returns None for segments that are not on it:
+    /// vowels, nasals, liquids, clicks and implosives.
+    pub fn strength(segment: Segment) -> Option<Strength>
+    {
+        match segment.phonet
+        {
+            Consonant {manner, airstream: PulmonicEgressive, ..} =>
+            {
+                let by_manner = manner_strength(manner)?;
+                if segment.length == Long { Some(Geminate) } else { Some(by_manner) }
+            },
+            _ => None,
+        }
+    }
+
+    fn scale_strength(segment: Segment, options: &LenitionOptions) -> Option<Strength>
+    {
+        match strength(segment)?
+        {
+            Geminate if !options.scale.contains(&Geminate) => strength(Segment {length: Short, ..segment}),
+            x => Some(x),
+        }
+    }
+
+    fn weaker_on_scale(strength: Strength, options: &LenitionOptions) -> Option<Strength>
+    {
+        options.scale.iter().filter(|x| **x > strength).min().copied()
+    }
+
+    fn stronger_on_scale(strength: Strength, options: &LenitionOptions) -> Option<Strength>
+    {
+        options.scale.iter().filter(|x| **x < strength).max().copied()
+    }
+
+    fn shifted_place(place: Place, options: &LenitionOptions, weakening: bool) -> Place
+    {
+        for (from, to) in options.place_shifts.iter()
+        {
+            if weakening && place == *from
+            {
+                return *to;
+            }
+            if !weakening && place == *to
+            {
+                return *from;
+            }
+        }
+        place
+    }
+
+    // Changes the manner of a consonant to the one for a strength,
+    // shifting the place if it goes between stops and continuants.
+    fn with_strength(segment: Segment, old: Strength, new: Strength, options: &LenitionOptions) -> Segment
+    {
+        match (segment.phonet, strength_manner(new))
+        {
+            (Consonant {vocal_folds, place, airstream, ..}, Some(manner)) =>
+            {
+                let place = if is_continuant(old) != is_continuant(new)
+                            {
+                                shifted_place(place, options, is_continuant(new))
+                            }
+                            else
+                            {
+                                place
+                            };
+                Segment {phonet: Consonant {vocal_folds, place, manner, airstream}, ..segment}
+            },
+            _ => segment,
+        }
+    }
+
+
+    /// Weakens a segment by one step. Returns None if
+    /// the segment is deleted. A geminate becomes a single consonant,
+    /// then (if voicing is on) a voiceless consonant loses its
+    /// aspiration and then becomes voiced, and after that
+    /// its manner moves down the scale.
+    /// Segments that are not on the scale are returned unchanged.
+    pub fn lenite(segment: Segment, options: &LenitionOptions) -> Option<Segment>
+    {
+        let current = match scale_strength(segment, options)
+        {
+            Some(x) => x,
+            None    => return Some(segment),
+        };
+        let (vocal_folds, place, manner, airstream) = match segment.phonet
+        {
+            Consonant {vocal_folds, place, manner, airstream} => (vocal_folds, place, manner, airstream),
+            Vowel {..} => return Some(segment),
+        };
+        if options.only_voiced && vocal_folds != Voiced && vocal_folds != VoicedAspirated
+        {
+            return Some(segment);
+        }
+
+        if current == Geminate
+        {
+            return Some(Segment {length: Short, ..segment});
+        }
+        if options.voicing && current != Approximant
+        {
+            match vocal_folds
+            {
+                VoicelessAspirated => return Some(Segment {phonet: Consonant {vocal_folds: Voiceless, place, manner, airstream}, ..segment}),
+                Voiceless          => return Some(Segment {phonet: Consonant {vocal_folds: Voiced, place, manner, airstream}, ..segment}),
+                _ => (),
+            }
+        }
+        match weaker_on_scale(current, options)
+        {
+            Some(Deleted) => None,
+            Some(weaker)  => Some(with_strength(segment, current, weaker, options)),
+            None          => Some(segment),
+        }
+    }
+
+    /// Strengthens a segment by one step, the reverse of lenite:
+    /// the manner moves up the scale, then (if voicing is on)
+    /// a voiced stop becomes voiceless, then it becomes a geminate.
+    pub fn fortify(segment: Segment, options: &LenitionOptions) -> Segment
+    {
+        let current = match scale_strength(segment, options)
+        {
+            Some(x) => x,
+            None    => return segment,
+        };
+        let (vocal_folds, place, manner, airstream) = match segment.phonet
+        {
+            Consonant {vocal_folds, place, manner, airstream} => (vocal_folds, place, manner, airstream),
+            Vowel {..} => return segment,
+        };
+
+        match stronger_on_scale(current, options)
+        {
+            Some(Geminate) if options.voicing && (vocal_folds == Voiced || vocal_folds == VoicedAspirated) =>
+              Segment {phonet: Consonant {vocal_folds: Voiceless, place, manner, airstream}, ..segment},
+            Some(Geminate) => Segment {length: Long, ..segment},
+            Some(stronger) => with_strength(segment, current, stronger, options),
+            None           => segment,
+        }
+    }
+
+    /// Lenites every segment of a sequence by one step,
+    /// leaving out the ones that are deleted.
+    pub fn lenite_all(segments: &[Segment], options: &LenitionOptions) -> Vec<Segment>
+    {
+        segments.iter().filter_map(|segment| lenite(*segment, options)).collect()
+    }
+
+    /// Fortifies every segment of a sequence by one step.
+    pub fn fortify_all(segments: &[Segment], options: &LenitionOptions) -> Vec<Segment>
+    {
+        segments.iter().map(|segment| fortify(*segment, options)).collect()
+    }
+
+
+    #[cfg(test)]
+    mod lenition_tests
+    {
+        use super::*;
+        use crate::grapheme::transcription::transcription::{parse_transcription, construct_segments_transcription};
+
+        fn lenite_text(text: &str, options: &LenitionOptions) -> String
+        {
+            construct_segments_transcription(&lenite_all(&parse_transcription(text), options))
+        }
+
+        #[test]
+        fn lenite_test_spanish_spirantization()
+        {
+            assert_eq!(lenite_text("bdgt", &LenitionOptions::spanish_spirantization()), "βðɣt");
+        }
+
+        #[test]
+        fn lenite_test_steps_down_the_whole_scale()
+        {
+            let options = LenitionOptions::default();
+            let mut segments = parse_transcription("tː");
+            let mut steps = Vec::new();
+            while let Some(segment) = segments.first()
+            {
+                steps.push(construct_segments_transcription(&segments));
+                segments = lenite_all(&[*segment], &options);
+            }
+            assert_eq!(steps, ["tː", "t", "d", "d͡z", "z", "ɹ"]);
+        }
+
+        #[test]
+        fn fortify_test_reverses_celtic_lenition()
+        {
+            let options = LenitionOptions::celtic_lenition();
+            let v = parse_transcription("v")[0];
+            assert_eq!(construct_segments_transcription(&[fortify(v, &options)]), "b");
+        }
+    }
+}
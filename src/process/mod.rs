@@ -0,0 +1,8 @@
+pub mod assimilation;
+pub mod english_allophony;
+pub mod harmony;
+pub mod lenition;
+pub mod reduction;
+pub mod secondary_articulation;
+pub mod sequence;
+pub mod vowel_shift;
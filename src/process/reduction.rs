@@ -0,0 +1,170 @@
+pub mod reduction
+{
+    use crate::lib::{Phonet, Phonet::*, Height::*, Backness::*, Rounding::*, VocalFolds::*, Manner::*};
+    use crate::segment::segment::{Segment, Length::*, Stress, Stress::*, syllable_stress};
+
+    /// The mid central vowel ə.
+    pub static SCHWA: Phonet = Vowel {height: Mid, backness: Central, rounding: UnmarkedRounding, vocal_folds: Voiced};
+
+    /// The close central vowel ɨ, the other common reduced vowel.
+    pub static BARRED_I: Phonet = Vowel {height: Close, backness: Central, rounding: Unrounded, vocal_folds: Voiced};
+
+
+    /// How unstressed vowels should be reduced.
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub struct ReductionOptions
+    {
+        // Full vowels and the reduced vowels they become.
+        pub targets: Vec<(Phonet, Phonet)>,
+
+        // What a vowel that is not in the table becomes.
+        // None leaves those vowels as they are.
+        pub default_target: Option<Phonet>,
+
+        // Whether vowels with secondary stress are reduced too.
+        pub reduce_secondary: bool,
+
+        // Whether an unstressed schwa between two consonants is deleted
+        // when the consonant after it is a sonorant followed by a vowel
+        // (as in camera, ˈkæmərə → ˈkæmrə).
+        pub delete_schwa: bool
+    }
+
+    impl Default for ReductionOptions
+    {
+        /// Every unstressed vowel becomes ə.
+        fn default() -> ReductionOptions
+        {
+            ReductionOptions { targets: Vec::new()
+                             , default_target: Some(SCHWA)
+                             , reduce_secondary: false
+                             , delete_schwa: false
+                             }
+        }
+    }
+
+    impl ReductionOptions
+    {
+        /// English: unstressed i and ɪ become ɨ (as in roses),
+        /// the other unstressed vowels become ə, and schwa is deleted
+        /// before a sonorant and a vowel.
+        pub fn english() -> ReductionOptions
+        {
+            let i = Vowel {height: Close, backness: Front, rounding: Unrounded, vocal_folds: Voiced};
+            let near_i = Vowel {height: NearClose, backness: Front, rounding: Unrounded, vocal_folds: Voiced};
+            ReductionOptions { targets: vec![(i, BARRED_I), (near_i, BARRED_I)]
+                             , default_target: Some(SCHWA)
+                             , reduce_secondary: false
+                             , delete_schwa: true
+                             }
+        }
+    }
+
+
+    /// The reduced vowel a full vowel becomes,
+    /// following the table of the options.
+    pub fn reduced_vowel(phonet: Phonet, options: &ReductionOptions) -> Phonet
+    {
+        match phonet
+        {
+            Vowel {..} =>
+            {
+                let target = options.targets.iter().find(|(full, _)| *full == phonet).map(|(_, reduced)| *reduced);
+                target.or(options.default_target).unwrap_or(phonet)
+            },
+            consonant => consonant,
+        }
+    }
+
+    fn reduces(stress: Stress, options: &ReductionOptions) -> bool
+    {
+        stress == Unstressed || (stress == Secondary && options.reduce_secondary)
+    }
+
+    fn is_consonant(segment: Option<&Segment>) -> bool
+    {
+        matches!(segment, Some(Segment {phonet: Consonant {..}, ..}))
+    }
+
+    fn is_sonorant(segment: Option<&Segment>) -> bool
+    {
+        matches!(segment, Some(Segment {phonet: Consonant {manner: Nasal, ..}, ..})
+                        | Some(Segment {phonet: Consonant {manner: Approximant, ..}, ..})
+                        | Some(Segment {phonet: Consonant {manner: LateralApproximant, ..}, ..})
+                        | Some(Segment {phonet: Consonant {manner: TapOrFlap, ..}, ..})
+                        | Some(Segment {phonet: Consonant {manner: Trill, ..}, ..}))
+    }
+
+    fn is_vowel(segment: Option<&Segment>) -> bool
+    {
+        matches!(segment, Some(Segment {phonet: Vowel {..}, ..}))
+    }
+
+    /// Reduces every vowel of an unstressed syllable, using the stress
+    /// marks of the transcription (see syllable_stress). Reduced vowels
+    /// are also made short, and a diphthong becomes a single vowel.
+    /// If the options say so, schwas that are unstressed, between two
+    /// consonants, and before a sonorant and a vowel, are then deleted.
+    pub fn reduce_vowels(segments: &[Segment], options: &ReductionOptions) -> Vec<Segment>
+    {
+        let mut reduced: Vec<Segment> = Vec::new();
+        for (i, segment) in segments.iter().enumerate()
+        {
+            if !is_vowel(Some(segment)) || !reduces(syllable_stress(segments, i), options)
+            {
+                reduced.push(*segment);
+            }
+            // A diphthong is reduced to one vowel, so its second part goes.
+            else if i == 0 || !is_vowel(segments.get(i - 1))
+            {
+                reduced.push(Segment {phonet: reduced_vowel(segment.phonet, options), length: Short, ..*segment});
+            }
+        }
+
+        if !options.delete_schwa
+        {
+            return reduced;
+        }
+        let mut result: Vec<Segment> = Vec::new();
+        for (i, segment) in reduced.iter().enumerate()
+        {
+            let deleted = segment.phonet == SCHWA
+                       && syllable_stress(&reduced, i) == Unstressed
+                       && i > 0 && is_consonant(reduced.get(i - 1))
+                       && is_sonorant(reduced.get(i + 1))
+                       && is_vowel(reduced.get(i + 2));
+            if !deleted
+            {
+                result.push(*segment);
+            }
+        }
+        result
+    }
+
+
+    #[cfg(test)]
+    mod reduction_tests
+    {
+        use super::*;
+        use crate::grapheme::transcription::transcription::{parse_transcription, construct_segments_transcription};
+
+        fn reduce_text(text: &str, options: &ReductionOptions) -> String
+        {
+            construct_segments_transcription(&reduce_vowels(&parse_transcription(text), options))
+        }
+
+        #[test]
+        fn reduce_vowels_test_english()
+        {
+            assert_eq!(reduce_text("ˈfoʊtoʊˌgræf", &ReductionOptions::english()), "ˈfoʊtəˌgræf");
+            assert_eq!(reduce_text("ˈɹoʊzɪz", &ReductionOptions::english()), "ˈɹoʊzɨz");
+        }
+
+        #[test]
+        fn reduce_vowels_test_schwa_deletion()
+        {
+            assert_eq!(reduce_text("ˈkæmərə", &ReductionOptions::english()), "ˈkæmrə");
+            assert_eq!(reduce_text("ˈkæmərə", &ReductionOptions::default()), "ˈkæmərə");
+        }
+    }
+}
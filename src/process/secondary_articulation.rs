@@ -0,0 +1,203 @@
+pub mod secondary_articulation
+{
+    use crate::lib::{Phonet, Phonet::*, Place::*, Manner::*, Height::*, Backness::*, Rounding::*};
+    use crate::segment::segment::{Segment, SecondaryArticulation, SecondaryArticulation::*};
+
+    /// Whether palatalization only adds a secondary articulation
+    /// (k → kʲ), or also moves the consonant towards the palate
+    /// where it can (k → t͡ʃ, s → ʃ, n → ɲ).
+    #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+    pub enum PalatalizationMode
+    {
+        SecondaryOnly,
+        Full
+    }
+
+
+    /// Gives a consonant a secondary articulation, replacing
+    /// the one it had. Vowels stay as they are.
+    pub fn with_secondary_articulation(segment: Segment, secondary: SecondaryArticulation) -> Segment
+    {
+        match segment.phonet
+        {
+            Consonant {..} => Segment {secondary, ..segment},
+            Vowel {..}     => segment,
+        }
+    }
+
+    /// Removes the secondary articulation of a segment.
+    pub fn without_secondary_articulation(segment: Segment) -> Segment
+    {
+        Segment {secondary: Plain, ..segment}
+    }
+
+    // The consonant a palatalized consonant becomes in full
+    // palatalization, if its place moves at all.
+    fn fully_palatalized_phonet(phonet: Phonet) -> Option<Phonet>
+    {
+        match phonet
+        {
+            Consonant {vocal_folds, place: Velar, manner: Plosive, airstream} =>
+              Some(Consonant {vocal_folds, place: PostAlveolar, manner: Affricate, airstream}),
+            Consonant {vocal_folds, place: Velar, manner: Fricative, airstream} =>
+              Some(Consonant {vocal_folds, place: PostAlveolar, manner: Fricative, airstream}),
+            Consonant {vocal_folds, place: Alveolar, manner: Plosive, airstream} =>
+              Some(Consonant {vocal_folds, place: Alveolar, manner: Affricate, airstream}),
+            Consonant {vocal_folds, place: Alveolar, manner: Fricative, airstream} =>
+              Some(Consonant {vocal_folds, place: PostAlveolar, manner: Fricative, airstream}),
+            Consonant {vocal_folds, place: Alveolar, manner: Nasal, airstream} =>
+              Some(Consonant {vocal_folds, place: Palatal, manner: Nasal, airstream}),
+            Consonant {vocal_folds, place: Alveolar, manner: LateralApproximant, airstream} =>
+              Some(Consonant {vocal_folds, place: Palatal, manner: LateralApproximant, airstream}),
+            _ => None,
+        }
+    }
+
+    /// Palatalizes a consonant. In full palatalization a consonant
+    /// whose place moves loses any secondary articulation it had,
+    /// and the others become secondary palatalized as well.
+    pub fn palatalized(segment: Segment, mode: PalatalizationMode) -> Segment
+    {
+        match (mode, fully_palatalized_phonet(segment.phonet))
+        {
+            (PalatalizationMode::Full, Some(phonet)) => Segment {phonet, secondary: Plain, ..segment},
+            _ => with_secondary_articulation(segment, Palatalized),
+        }
+    }
+
+    /// Labializes a consonant (k → kʷ).
+    pub fn labialized(segment: Segment) -> Segment
+    {
+        with_secondary_articulation(segment, Labialized)
+    }
+
+    /// Velarizes a consonant (l → ɫ).
+    pub fn velarized(segment: Segment) -> Segment
+    {
+        with_secondary_articulation(segment, Velarized)
+    }
+
+    /// Pharyngealizes a consonant (t → tˤ), as in the emphatic
+    /// consonants of Arabic.
+    pub fn pharyngealized(segment: Segment) -> Segment
+    {
+        with_secondary_articulation(segment, Pharyngealized)
+    }
+
+
+    // The open front vowels (a, æ) do not count.
+    fn is_front(segment: Segment) -> bool
+    {
+        match segment.phonet
+        {
+            Vowel {height: Open, ..} | Vowel {height: NearOpen, ..} => false,
+            Vowel {backness, ..} => backness == Front,
+            Consonant {place, manner, ..} => place == Palatal && manner == Approximant,
+        }
+    }
+
+    fn is_rounded(segment: Segment) -> bool
+    {
+        matches!(segment.phonet, Vowel {rounding: Rounded, ..}
+                               | Consonant {place: LabialVelar, manner: Approximant, ..})
+    }
+
+    // The segments that stop emphasis from spreading in Arabic:
+    // the close front vowels, j, and the postalveolar consonants.
+    fn blocks_emphasis(segment: Segment) -> bool
+    {
+        matches!(segment.phonet, Vowel {height: Close, backness: Front, ..}
+                               | Vowel {height: NearClose, backness: Front, ..}
+                               | Consonant {place: Palatal, ..}
+                               | Consonant {place: PostAlveolar, ..})
+    }
+
+    // Applies a change to every consonant whose next segment
+    // satisfies the trigger.
+    fn change_before(segments: &[Segment], trigger: fn(Segment) -> bool, change: impl Fn(Segment) -> Segment) -> Vec<Segment>
+    {
+        let mut result: Vec<Segment> = segments.to_vec();
+        for (i, segment) in segments.iter().enumerate()
+        {
+            let triggered = segments.get(i + 1).map(|next| trigger(*next)).unwrap_or(false);
+            if triggered && matches!(segment.phonet, Consonant {..})
+            {
+                result[i] = change(*segment);
+            }
+        }
+        result
+    }
+
+    /// Palatalizes every consonant before a front vowel or j,
+    /// as in the Slavic palatalizations. The open front vowels
+    /// (a, æ) do not trigger it.
+    pub fn palatalize_before_front_vowels(segments: &[Segment], mode: PalatalizationMode) -> Vec<Segment>
+    {
+        change_before(segments, is_front, |segment| palatalized(segment, mode))
+    }
+
+    /// Labializes every consonant before a rounded vowel or w,
+    /// as in many Bantu languages.
+    pub fn labialize_before_rounded_vowels(segments: &[Segment]) -> Vec<Segment>
+    {
+        change_before(segments, is_rounded, labialized)
+    }
+
+    /// Spreads pharyngealization from every emphatic (pharyngealized)
+    /// consonant to the segments on both sides of it, vowels included,
+    /// until a close front vowel, j or a postalveolar blocks it.
+    pub fn spread_emphasis(segments: &[Segment]) -> Vec<Segment>
+    {
+        let mut result: Vec<Segment> = segments.to_vec();
+        let sources = segments.iter().enumerate().filter(|(_, segment)| segment.secondary == Pharyngealized);
+        for (source, _) in sources
+        {
+            let left = (0..source).rev();
+            let right = source + 1..segments.len();
+            for range in [left.collect::<Vec<usize>>(), right.collect()]
+            {
+                for i in range
+                {
+                    if blocks_emphasis(segments[i])
+                    {
+                        break;
+                    }
+                    result[i] = Segment {secondary: Pharyngealized, ..result[i]};
+                }
+            }
+        }
+        result
+    }
+
+
+    #[cfg(test)]
+    mod secondary_articulation_tests
+    {
+        use super::*;
+        use crate::grapheme::transcription::transcription::{parse_transcription, construct_segments_transcription};
+
+        #[test]
+        fn palatalize_before_front_vowels_test()
+        {
+            let segments = parse_transcription("kekaxisa");
+            let full = palatalize_before_front_vowels(&segments, PalatalizationMode::Full);
+            assert_eq!(construct_segments_transcription(&full), "t͡ʃekaʃisa");
+            let secondary = palatalize_before_front_vowels(&segments, PalatalizationMode::SecondaryOnly);
+            assert_eq!(construct_segments_transcription(&secondary), "kʲekaxʲisa");
+        }
+
+        #[test]
+        fn labialize_before_rounded_vowels_test()
+        {
+            let segments = parse_transcription("kuta");
+            assert_eq!(construct_segments_transcription(&labialize_before_rounded_vowels(&segments)), "kʷuta");
+        }
+
+        #[test]
+        fn spread_emphasis_test_blocked_by_close_front_vowel()
+        {
+            let segments = parse_transcription("satˤabit");
+            assert_eq!(construct_segments_transcription(&spread_emphasis(&segments)), "sˤaˤtˤaˤbˤit");
+        }
+    }
+}
@@ -0,0 +1,268 @@
+pub mod sequence
+{
+    use std::fmt;
+    use crate::lib::{Feature, feature_value, with_feature_value, Phonet::*};
+    use crate::segment::segment::Segment;
+    use crate::grapheme::transcription::transcription::construct_segment_transcription;
+
+    /// A condition on a neighbouring segment.
+    /// It is given None at the edge of the sequence.
+    pub type Condition = fn(Option<Segment>) -> bool;
+
+    /// Any neighbour, including the edge of the sequence.
+    pub fn anything(_: Option<Segment>) -> bool
+    {
+        true
+    }
+
+    /// The edge of the sequence (a word boundary).
+    pub fn edge(neighbour: Option<Segment>) -> bool
+    {
+        neighbour.is_none()
+    }
+
+    /// A vowel.
+    pub fn vowel(neighbour: Option<Segment>) -> bool
+    {
+        matches!(neighbour, Some(Segment {phonet: Vowel {..}, ..}))
+    }
+
+    /// A consonant.
+    pub fn consonant(neighbour: Option<Segment>) -> bool
+    {
+        matches!(neighbour, Some(Segment {phonet: Consonant {..}, ..}))
+    }
+
+
+    /// Where an operation applies: what has to come before
+    /// and what has to come after.
+    #[derive(Debug, Clone, Copy)]
+    pub struct Context
+    {
+        pub before: Condition,
+        pub after: Condition
+    }
+
+    impl Context
+    {
+        /// Everywhere.
+        pub fn anywhere() -> Context
+        {
+            Context {before: anything, after: anything}
+        }
+
+        /// Between the two conditions.
+        pub fn between(before: Condition, after: Condition) -> Context
+        {
+            Context {before, after}
+        }
+    }
+
+
+    /// One change to a sequence of segments.
+    /// The indexes are positions in the input.
+    #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+    pub enum SequenceChange
+    {
+        Inserted {index: usize, segment: Segment},
+        Deleted {index: usize, segment: Segment},
+        Metathesized {index: usize, first: Segment, second: Segment},
+        Coalesced {index: usize, first: Segment, second: Segment, result: Segment}
+    }
+
+    impl fmt::Display for SequenceChange
+    {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            let ipa = |segment: &Segment| construct_segment_transcription(*segment);
+            match self
+            {
+                SequenceChange::Inserted {index, segment} =>
+                  write!(f, "inserted {} at {}", ipa(segment), index),
+                SequenceChange::Deleted {index, segment} =>
+                  write!(f, "deleted {} at {}", ipa(segment), index),
+                SequenceChange::Metathesized {index, first, second} =>
+                  write!(f, "{}{} → {}{} at {}", ipa(first), ipa(second), ipa(second), ipa(first), index),
+                SequenceChange::Coalesced {index, first, second, result} =>
+                  write!(f, "{}{} → {} at {}", ipa(first), ipa(second), ipa(result), index),
+            }
+        }
+    }
+
+    /// A sequence after an operation, and what the operation did.
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub struct SequenceResult
+    {
+        pub segments: Vec<Segment>,
+        pub changes: Vec<SequenceChange>
+    }
+
+
+    /// Inserts a segment at every position where the context holds,
+    /// for example ə between two consonants. Positions at the edges
+    /// are included, with None as the missing neighbour.
+    pub fn epenthesize(segments: &[Segment], inserted: Segment, context: &Context) -> SequenceResult
+    {
+        let mut result: Vec<Segment> = Vec::new();
+        let mut changes: Vec<SequenceChange> = Vec::new();
+        for index in 0..=segments.len()
+        {
+            let before = if index > 0 { segments.get(index - 1).copied() } else { None };
+            let after = segments.get(index).copied();
+            if (context.before)(before) && (context.after)(after)
+            {
+                result.push(inserted);
+                changes.push(SequenceChange::Inserted {index, segment: inserted});
+            }
+            if let Some(segment) = after
+            {
+                result.push(segment);
+            }
+        }
+        SequenceResult {segments: result, changes}
+    }
+
+    /// Deletes every segment that matches, where the context holds.
+    /// The context looks at the neighbours in the input, so
+    /// one deletion does not make room for another.
+    pub fn delete(segments: &[Segment], target: fn(Segment) -> bool, context: &Context) -> SequenceResult
+    {
+        let mut result: Vec<Segment> = Vec::new();
+        let mut changes: Vec<SequenceChange> = Vec::new();
+        for (index, segment) in segments.iter().enumerate()
+        {
+            let before = if index > 0 { segments.get(index - 1).copied() } else { None };
+            let after = segments.get(index + 1).copied();
+            if target(*segment) && (context.before)(before) && (context.after)(after)
+            {
+                changes.push(SequenceChange::Deleted {index, segment: *segment});
+            }
+            else
+            {
+                result.push(*segment);
+            }
+        }
+        SequenceResult {segments: result, changes}
+    }
+
+    /// Swaps every pair of adjacent segments where the first
+    /// matches `first` and the second matches `second`,
+    /// for example sk → ks. Pairs are found from left to right
+    /// and do not overlap.
+    pub fn metathesize(segments: &[Segment], first: fn(Segment) -> bool, second: fn(Segment) -> bool) -> SequenceResult
+    {
+        let mut result: Vec<Segment> = segments.to_vec();
+        let mut changes: Vec<SequenceChange> = Vec::new();
+        let mut index = 0;
+        while index + 1 < segments.len()
+        {
+            let (a, b) = (segments[index], segments[index + 1]);
+            if first(a) && second(b)
+            {
+                result.swap(index, index + 1);
+                changes.push(SequenceChange::Metathesized {index, first: a, second: b});
+                index += 2;
+            }
+            else
+            {
+                index += 1;
+            }
+        }
+        SequenceResult {segments: result, changes}
+    }
+
+    /// Replaces every pair of adjacent segments that matches with
+    /// the one segment that `combine` makes of them (for example a+i → e).
+    /// If `combine` returns None the pair is left as it is.
+    /// Pairs are found from left to right and do not overlap.
+    pub fn coalesce(segments: &[Segment],
+                    first: fn(Segment) -> bool,
+                    second: fn(Segment) -> bool,
+                    combine: impl Fn(Segment, Segment) -> Option<Segment>) -> SequenceResult
+    {
+        let mut result: Vec<Segment> = Vec::new();
+        let mut changes: Vec<SequenceChange> = Vec::new();
+        let mut index = 0;
+        while index < segments.len()
+        {
+            let a = segments[index];
+            let combined = match segments.get(index + 1)
+            {
+                Some(b) if first(a) && second(*b) => combine(a, *b).map(|result| (*b, result)),
+                _ => None,
+            };
+            match combined
+            {
+                Some((b, coalesced)) =>
+                {
+                    result.push(coalesced);
+                    changes.push(SequenceChange::Coalesced {index, first: a, second: b, result: coalesced});
+                    index += 2;
+                },
+                None =>
+                {
+                    result.push(a);
+                    index += 1;
+                },
+            }
+        }
+        SequenceResult {segments: result, changes}
+    }
+
+    /// Combines two segments into one: the first segment, with the
+    /// listed features taken from the second. Returns None if the
+    /// second segment does not have one of the features, or the first
+    /// cannot take it (a vowel cannot take a place, for example).
+    pub fn combined_features(first: Segment, second: Segment, from_second: &[Feature]) -> Option<Segment>
+    {
+        let mut phonet = first.phonet;
+        for feature in from_second.iter()
+        {
+            phonet = with_feature_value(phonet, feature_value(second.phonet, *feature)?)?;
+        }
+        Some(Segment {phonet, ..first})
+    }
+
+
+    #[cfg(test)]
+    mod sequence_tests
+    {
+        use super::*;
+        use crate::grapheme::transcription::transcription::{parse_transcription, construct_segments_transcription};
+
+        fn is_vowel(segment: Segment) -> bool
+        {
+            vowel(Some(segment))
+        }
+
+        #[test]
+        fn epenthesize_test_between_consonants_and_log()
+        {
+            let schwa = parse_transcription("ə")[0];
+            let result = epenthesize(&parse_transcription("pliks"), schwa, &Context::between(consonant, consonant));
+            assert_eq!(construct_segments_transcription(&result.segments), "pəlikəs");
+            let log: Vec<String> = result.changes.iter().map(|change| change.to_string()).collect();
+            assert_eq!(log, ["inserted ə at 1", "inserted ə at 4"]);
+        }
+
+        #[test]
+        fn metathesize_and_delete_test()
+        {
+            let is_s = |segment: Segment| construct_segment_transcription(segment) == "s";
+            let is_k = |segment: Segment| construct_segment_transcription(segment) == "k";
+            let result = metathesize(&parse_transcription("aska"), is_s, is_k);
+            assert_eq!(construct_segments_transcription(&result.segments), "aksa");
+            let result = delete(&parse_transcription("aska"), is_vowel, &Context::between(anything, edge));
+            assert_eq!(construct_segments_transcription(&result.segments), "ask");
+        }
+
+        #[test]
+        fn coalesce_test_backness_from_second()
+        {
+            let result = coalesce(&parse_transcription("koi"), is_vowel, is_vowel,
+                                  |a, b| combined_features(a, b, &[Feature::Backness]));
+            assert_eq!(construct_segments_transcription(&result.segments), "kø");
+            assert_eq!(result.changes[0].to_string(), "oi → ø at 1");
+        }
+    }
+}
@@ -0,0 +1,348 @@
+pub mod vowel_shift
+{
+    use std::fmt;
+    use crate::lib::{Phonet, Phonet::*, Height, Height::*, Backness::*, Rounding::*, VocalFolds::*,
+                     HEIGHT_STATES, BACKNESS_STATES};
+    use crate::segment::segment::{Segment, Length::*};
+    use crate::grapheme::transcription::transcription::construct_segment_transcription;
+
+    // Moves a value one step along a list of states, staying put
+    // at either end of the list, or if the value is not in the list
+    // (for unmarked values).
+    fn step<T: PartialEq + Copy>(states: &[T], value: T, forward: bool) -> T
+    {
+        match states.iter().position(|x| *x == value)
+        {
+            Some(i) if forward && i + 1 < states.len() => states[i + 1],
+            Some(i) if !forward && i > 0               => states[i - 1],
+            _                                          => value,
+        }
+    }
+
+    // Moves a height one step towards Mid.
+    fn centralized_height(height: Height) -> Height
+    {
+        let mid = HEIGHT_STATES.iter().position(|x| *x == Mid).unwrap();
+        match HEIGHT_STATES.iter().position(|x| *x == height)
+        {
+            Some(i) if i < mid => HEIGHT_STATES[i + 1],
+            Some(i) if i > mid => HEIGHT_STATES[i - 1],
+            _                  => height,
+        }
+    }
+
+    /// Raises a vowel one step (for example e → ɪ).
+    /// A close vowel, or a consonant, is returned unchanged.
+    pub fn raised_vowel(phonet: Phonet) -> Phonet
+    {
+        match phonet
+        {
+            Vowel {height, backness, rounding, vocal_folds} => Vowel {height: step(&HEIGHT_STATES, height, false), backness, rounding, vocal_folds},
+            consonant => consonant,
+        }
+    }
+
+    /// Lowers a vowel one step (for example ɛ → æ).
+    /// An open vowel, or a consonant, is returned unchanged.
+    /// e becomes a mid front vowel, which has no symbol.
+    pub fn lowered_vowel(phonet: Phonet) -> Phonet
+    {
+        match phonet
+        {
+            Vowel {height, backness, rounding, vocal_folds} => Vowel {height: step(&HEIGHT_STATES, height, true), backness, rounding, vocal_folds},
+            consonant => consonant,
+        }
+    }
+
+    /// Moves a vowel one step forward (for example u → ʉ).
+    pub fn fronted_vowel(phonet: Phonet) -> Phonet
+    {
+        match phonet
+        {
+            Vowel {height, backness, rounding, vocal_folds} => Vowel {height, backness: step(&BACKNESS_STATES, backness, false), rounding, vocal_folds},
+            consonant => consonant,
+        }
+    }
+
+    /// Moves a vowel one step back (for example i → ɨ).
+    pub fn backed_vowel(phonet: Phonet) -> Phonet
+    {
+        match phonet
+        {
+            Vowel {height, backness, rounding, vocal_folds} => Vowel {height, backness: step(&BACKNESS_STATES, backness, true), rounding, vocal_folds},
+            consonant => consonant,
+        }
+    }
+
+    /// Makes a vowel rounded (for example i → y).
+    pub fn rounded_vowel(phonet: Phonet) -> Phonet
+    {
+        match phonet
+        {
+            Vowel {height, backness, vocal_folds, ..} => Vowel {height, backness, rounding: Rounded, vocal_folds},
+            consonant => consonant,
+        }
+    }
+
+    /// Makes a vowel unrounded (for example u → ɯ).
+    pub fn unrounded_vowel(phonet: Phonet) -> Phonet
+    {
+        match phonet
+        {
+            Vowel {height, backness, vocal_folds, ..} => Vowel {height, backness, rounding: Unrounded, vocal_folds},
+            consonant => consonant,
+        }
+    }
+
+    /// Moves a vowel to central backness,
+    /// and one step in height towards mid (for example æ → ɜ).
+    /// Some of the vowels it gives have no symbol, such as
+    /// the near-close central vowel that i becomes.
+    pub fn centralized_vowel(phonet: Phonet) -> Phonet
+    {
+        match phonet
+        {
+            Vowel {height, backness: _, rounding, vocal_folds} =>
+              Vowel {height: centralized_height(height), backness: Central, rounding, vocal_folds},
+            consonant => consonant,
+        }
+    }
+
+
+    /// One change to the quality of a vowel.
+    #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+    pub enum VowelMove
+    {
+        Raise, Lower, Front, Back, Round, Unround, Centralize,
+
+        // The vowel becomes a diphthong. Diphthongs cannot be
+        // represented as a single segment, so the vowel leaves
+        // the inventory of monophthongs.
+        Diphthongize
+    }
+
+    impl VowelMove
+    {
+        /// Applies the move to a vowel. Returns None for Diphthongize.
+        pub fn apply(self, phonet: Phonet) -> Option<Phonet>
+        {
+            match self
+            {
+                VowelMove::Raise        => Some(raised_vowel(phonet)),
+                VowelMove::Lower        => Some(lowered_vowel(phonet)),
+                VowelMove::Front        => Some(fronted_vowel(phonet)),
+                VowelMove::Back         => Some(backed_vowel(phonet)),
+                VowelMove::Round        => Some(rounded_vowel(phonet)),
+                VowelMove::Unround      => Some(unrounded_vowel(phonet)),
+                VowelMove::Centralize   => Some(centralized_vowel(phonet)),
+                VowelMove::Diphthongize => None,
+            }
+        }
+    }
+
+
+    /// The moves that one vowel of an inventory undergoes, in order.
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub struct ShiftRule
+    {
+        pub target: Segment,
+        pub moves: Vec<VowelMove>
+    }
+
+    /// A set of vowel shifts that happen at the same time.
+    /// Each vowel is matched against the inventory before any
+    /// of them move, so one shift does not feed another.
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub struct ChainShift
+    {
+        pub name: String,
+        pub rules: Vec<ShiftRule>
+    }
+
+    impl ChainShift
+    {
+        /// The Great Vowel Shift of English, as a single step:
+        /// the long mid and low vowels rise (eː → iː, ɛː → eː, aː → ɛː,
+        /// ɔː → oː, oː → uː) and the long close vowels
+        /// iː and uː become diphthongs.
+        pub fn great_vowel_shift() -> ChainShift
+        {
+            let long = |height, backness, rounding| Segment { length: Long
+                                                            , ..Segment::new(Vowel {height, backness, rounding, vocal_folds: Voiced})
+                                                            };
+            ChainShift
+            { name: String::from("Great Vowel Shift")
+            , rules: vec![ ShiftRule {target: long(Close   , Front, Unrounded), moves: vec![VowelMove::Diphthongize]}
+                         , ShiftRule {target: long(Close   , Back , Rounded  ), moves: vec![VowelMove::Diphthongize]}
+                         , ShiftRule {target: long(CloseMid, Front, Unrounded), moves: vec![VowelMove::Raise, VowelMove::Raise]}
+                         , ShiftRule {target: long(OpenMid , Front, Unrounded), moves: vec![VowelMove::Raise, VowelMove::Raise]}
+                         , ShiftRule {target: long(Open    , Front, Unrounded), moves: vec![VowelMove::Raise, VowelMove::Raise]}
+                         , ShiftRule {target: long(OpenMid , Back , Rounded  ), moves: vec![VowelMove::Raise, VowelMove::Raise]}
+                         , ShiftRule {target: long(CloseMid, Back , Rounded  ), moves: vec![VowelMove::Raise, VowelMove::Raise]}
+                         ]
+            }
+        }
+    }
+
+    /// What happened to one segment of the inventory.
+    /// The result is None if it became a diphthong.
+    #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+    pub struct ShiftOutcome
+    {
+        pub before: Segment,
+        pub after: Option<Segment>
+    }
+
+    /// Two or more different segments that ended up the same.
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub struct Merger
+    {
+        pub sources: Vec<Segment>,
+        pub result: Segment
+    }
+
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub struct ChainShiftResult
+    {
+        pub outcomes: Vec<ShiftOutcome>,
+        pub mergers: Vec<Merger>
+    }
+
+    impl ChainShiftResult
+    {
+        /// The inventory after the shift, without duplicates
+        /// and without the vowels that became diphthongs.
+        pub fn inventory(&self) -> Vec<Segment>
+        {
+            let mut inventory: Vec<Segment> = Vec::new();
+            for after in self.outcomes.iter().filter_map(|outcome| outcome.after)
+            {
+                if !inventory.contains(&after)
+                {
+                    inventory.push(after);
+                }
+            }
+            inventory
+        }
+    }
+
+    impl fmt::Display for ChainShiftResult
+    {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            let mut lines: Vec<String> = Vec::new();
+            for outcome in self.outcomes.iter()
+            {
+                let after = match outcome.after
+                {
+                    Some(x) => construct_segment_transcription(x),
+                    None    => String::from("(diphthong)"),
+                };
+                lines.push(format!("{} → {}", construct_segment_transcription(outcome.before), after));
+            }
+            for merger in self.mergers.iter()
+            {
+                let sources: Vec<String> = merger.sources.iter().map(|x| construct_segment_transcription(*x)).collect();
+                lines.push(format!("merger: {} → {}", sources.join(", "), construct_segment_transcription(merger.result)));
+            }
+            write!(f, "{}", lines.join("\n"))
+        }
+    }
+
+
+    /// Applies a chain shift to every segment of an inventory
+    /// and reports the segments that merged.
+    /// Segments that no rule targets stay as they are.
+    pub fn simulate_chain_shift(inventory: &[Segment], shift: &ChainShift) -> ChainShiftResult
+    {
+        let outcomes: Vec<ShiftOutcome> = inventory.iter().map(|before| ShiftOutcome {before: *before, after: shifted(*before, shift)}).collect();
+
+        let mut mergers: Vec<Merger> = Vec::new();
+        for outcome in outcomes.iter()
+        {
+            let after = match outcome.after
+            {
+                Some(x) => x,
+                None    => continue,
+            };
+            if mergers.iter().any(|m| m.result == after)
+            {
+                continue;
+            }
+            let mut sources: Vec<Segment> = Vec::new();
+            for other in outcomes.iter().filter(|o| o.after == Some(after))
+            {
+                if !sources.contains(&other.before)
+                {
+                    sources.push(other.before);
+                }
+            }
+            if sources.len() > 1
+            {
+                mergers.push(Merger {sources, result: after});
+            }
+        }
+
+        ChainShiftResult {outcomes, mergers}
+    }
+
+    fn shifted(segment: Segment, shift: &ChainShift) -> Option<Segment>
+    {
+        match shift.rules.iter().find(|rule| rule.target == segment)
+        {
+            Some(rule) =>
+            {
+                let mut phonet = segment.phonet;
+                for step in rule.moves.iter()
+                {
+                    phonet = step.apply(phonet)?;
+                }
+                Some(Segment {phonet, ..segment})
+            },
+            None => Some(segment),
+        }
+    }
+
+
+    #[cfg(test)]
+    mod vowel_shift_tests
+    {
+        use super::*;
+        use crate::grapheme::transcription::transcription::parse_transcription;
+
+        #[test]
+        fn great_vowel_shift_test()
+        {
+            let inventory = parse_transcription("iːeːɛːaːɔːoːuː");
+            let result = simulate_chain_shift(&inventory, &ChainShift::great_vowel_shift());
+            assert!(result.mergers.is_empty());
+            assert_eq!(result.to_string().lines().nth(1), Some("eː → iː"));
+            assert_eq!(result.inventory().len(), 5);
+        }
+
+        #[test]
+        fn simulate_chain_shift_test_merger()
+        {
+            let inventory = parse_transcription("ie");
+            let raise_e = ChainShift {name: String::from("raising"), rules: vec![ShiftRule {target: inventory[1], moves: vec![VowelMove::Raise, VowelMove::Raise]}]};
+            let result = simulate_chain_shift(&inventory, &raise_e);
+            assert_eq!(result.mergers, vec![Merger {sources: inventory.clone(), result: inventory[0]}]);
+        }
+
+        #[test]
+        fn centralized_vowel_test()
+        {
+            let vowels = parse_transcription("æɜ");
+            assert_eq!(centralized_vowel(vowels[0].phonet), vowels[1].phonet);
+        }
+
+        #[test]
+        fn lowered_vowel_test()
+        {
+            let vowels = parse_transcription("ɛæeə");
+            assert_eq!(lowered_vowel(vowels[0].phonet), vowels[1].phonet);
+            assert_eq!(lowered_vowel(vowels[2].phonet), Vowel {height: Mid, backness: Front, rounding: Unrounded, vocal_folds: Voiced});
+            assert_ne!(lowered_vowel(vowels[2].phonet), vowels[3].phonet);
+        }
+    }
+}
@@ -0,0 +1,327 @@
+pub mod alternation
+{
+    use std::fmt;
+    use crate::lib::{Phonet, Feature, FeatureValue, ALL_FEATURES, feature_value};
+    use crate::segment::segment::Segment;
+    use crate::grapheme::transcription::transcription::construct_segments_transcription;
+    use crate::table::table::text_table;
+
+    /// Where an affix goes on its stem.
+    #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+    pub enum AffixPosition
+    {
+        Prefix,
+        Suffix
+    }
+
+    /// The values that a feature of a segment may have, such as
+    /// a place that is Alveolar or PostAlveolar.
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub struct FeatureCondition
+    {
+        pub feature: Feature,
+        pub values: Vec<FeatureValue>
+    }
+
+    impl FeatureCondition
+    {
+        /// Whether a phonet has one of the values. A phonet without
+        /// the feature (such as a vowel for place) does not.
+        pub fn holds(&self, phonet: Phonet) -> bool
+        {
+            feature_value(phonet, self.feature).map_or(false, |x| self.values.contains(&x))
+        }
+    }
+
+    impl fmt::Display for FeatureCondition
+    {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            let values: Vec<String> = self.values.iter().map(|x| x.to_string()).collect();
+            write!(f, "{} is {}", self.feature, values.join(" or "))
+        }
+    }
+
+    /// Where an allomorph is found, by the segment of the stem next to it.
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub enum Environment
+    {
+        // Next to a segment for which every condition holds.
+        Conditioned(Vec<FeatureCondition>),
+
+        // Wherever none of the allomorphs before it is found.
+        Elsewhere,
+
+        // The neighbouring segments do not tell it from the
+        // other allomorphs left (the choice may be lexical).
+        Unconditioned
+    }
+
+
+    /// One form of the affix, with the stems it was found on.
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub struct Allomorph
+    {
+        pub form: Vec<Segment>,
+        pub stems: Vec<Vec<Segment>>,
+        pub environment: Environment
+    }
+
+    /// The allomorphs of an affix, in the order that their environments
+    /// are to be checked in (the elsewhere case last), and the one
+    /// proposed as the underlying form.
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub struct AlternationAnalysis
+    {
+        pub position: AffixPosition,
+        pub allomorphs: Vec<Allomorph>,
+        // None if no allomorph was found.
+        pub underlying: Option<usize>,
+
+        // The pairs whose derived form is not the stem with an affix.
+        pub unanalyzed: Vec<(Vec<Segment>, Vec<Segment>)>
+    }
+
+    impl AlternationAnalysis
+    {
+        /// The proposed underlying form of the affix, if any allomorph was found.
+        pub fn underlying_form(&self) -> Option<&Allomorph>
+        {
+            self.underlying.map(|i| &self.allomorphs[i])
+        }
+
+        fn written(&self, form: &[Segment]) -> String
+        {
+            match self.position
+            {
+                AffixPosition::Prefix => format!("{}-", construct_segments_transcription(form)),
+                AffixPosition::Suffix => format!("-{}", construct_segments_transcription(form)),
+            }
+        }
+    }
+
+    impl fmt::Display for AlternationAnalysis
+    {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            let side = match self.position
+            {
+                AffixPosition::Prefix => "before",
+                AffixPosition::Suffix => "after",
+            };
+            let mut rows = vec![vec![String::from("allomorph"), String::from("environment"), String::from("stems")]];
+            for allomorph in self.allomorphs.iter()
+            {
+                let environment = match &allomorph.environment
+                {
+                    Environment::Conditioned(conditions) =>
+                      format!("{} {}", side, conditions.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(", ")),
+                    Environment::Elsewhere     => String::from("elsewhere"),
+                    Environment::Unconditioned => String::from("unconditioned"),
+                };
+                let stems: Vec<String> = allomorph.stems.iter().map(|x| construct_segments_transcription(x)).collect();
+                rows.push(vec![self.written(&allomorph.form), environment, stems.join(", ")]);
+            }
+            match self.underlying_form()
+            {
+                Some(allomorph) => write!(f, "{}\nunderlying form: /{}/", text_table(&rows), self.written(&allomorph.form)),
+                None            => write!(f, "{}\nno allomorphs found", text_table(&rows)),
+            }
+        }
+    }
+
+
+    // The affix, if the derived form is the stem with it added.
+    fn affix(position: AffixPosition, stem: &[Segment], derived: &[Segment]) -> Option<Vec<Segment>>
+    {
+        if stem.is_empty() || derived.len() <= stem.len()
+        {
+            return None;
+        }
+        match position
+        {
+            AffixPosition::Prefix => derived.ends_with(stem).then(|| derived[..derived.len() - stem.len()].to_vec()),
+            AffixPosition::Suffix => derived.starts_with(stem).then(|| derived[stem.len()..].to_vec()),
+        }
+    }
+
+    // The segment of the stem next to the affix.
+    fn neighbour(position: AffixPosition, stem: &[Segment]) -> Phonet
+    {
+        match position
+        {
+            AffixPosition::Prefix => stem[0].phonet,
+            AffixPosition::Suffix => stem[stem.len() - 1].phonet,
+        }
+    }
+
+    // For each feature that all the phonets have, the values they have.
+    fn shared_conditions(phonets: &[Phonet]) -> Vec<FeatureCondition>
+    {
+        ALL_FEATURES.iter().filter_map(|feature|
+        {
+            let mut values: Vec<FeatureValue> = Vec::new();
+            for phonet in phonets.iter()
+            {
+                let value = feature_value(*phonet, *feature)?;
+                if !values.contains(&value)
+                {
+                    values.push(value);
+                }
+            }
+            Some(FeatureCondition {feature: *feature, values})
+        }).collect()
+    }
+
+    fn excludes(conditions: &[FeatureCondition], others: &[Phonet]) -> bool
+    {
+        others.iter().all(|phonet| !conditions.iter().all(|x| x.holds(*phonet)))
+    }
+
+    // The fewest of the conditions that still tell the allomorph from the others:
+    // each is left out in turn, and stays out if no other neighbour then holds.
+    // They are tried from the last feature back to the first, vocal folds,
+    // so that the voicing of the neighbour is kept when it is enough.
+    fn distinguishing(conditions: Vec<FeatureCondition>, others: &[Phonet]) -> Vec<FeatureCondition>
+    {
+        let mut conditions = conditions;
+        for i in (0..conditions.len()).rev()
+        {
+            let mut fewer = conditions.clone();
+            fewer.remove(i);
+            if !fewer.is_empty() && excludes(&fewer, others)
+            {
+                conditions = fewer;
+            }
+        }
+        conditions
+    }
+
+    /// Finds the allomorphs of an affix from pairs of a stem and the stem
+    /// with the affix, such as (kæt, kæts) and (dɔg, dɔgz). The affix is
+    /// a prefix or a suffix, whichever explains more pairs. Going through
+    /// the allomorphs, the first one whose neighbouring segments share
+    /// feature values that no other allomorph left is found next to is
+    /// given the fewest of those values as its environment, until only
+    /// one is left, which is the elsewhere case. That one is proposed as
+    /// the underlying form, or if there is none, the most common one.
+    pub fn analyze_alternation(pairs: &[(Vec<Segment>, Vec<Segment>)]) -> AlternationAnalysis
+    {
+        let explained = |position: AffixPosition| pairs.iter().filter(|(stem, derived)| affix(position, stem, derived).is_some()).count();
+        let position = if explained(AffixPosition::Prefix) > explained(AffixPosition::Suffix) { AffixPosition::Prefix } else { AffixPosition::Suffix };
+
+        let mut allomorphs: Vec<Allomorph> = Vec::new();
+        let mut unanalyzed: Vec<(Vec<Segment>, Vec<Segment>)> = Vec::new();
+        for (stem, derived) in pairs.iter()
+        {
+            match affix(position, stem, derived)
+            {
+                Some(form) => match allomorphs.iter_mut().find(|x| x.form == form)
+                {
+                    Some(allomorph) => allomorph.stems.push(stem.clone()),
+                    None            => allomorphs.push(Allomorph {form, stems: vec![stem.clone()], environment: Environment::Unconditioned}),
+                },
+                None => unanalyzed.push((stem.clone(), derived.clone())),
+            }
+        }
+
+        // The allomorphs whose environment is not yet known.
+        let neighbours = |allomorph: &Allomorph| allomorph.stems.iter().map(|x| neighbour(position, x)).collect::<Vec<Phonet>>();
+        let mut left: Vec<usize> = (0..allomorphs.len()).collect();
+        let mut order: Vec<usize> = Vec::new();
+        while left.len() > 1
+        {
+            let found = left.iter().enumerate().find_map(|(n, i)|
+            {
+                let others: Vec<Phonet> = left.iter().filter(|x| *x != i).flat_map(|x| neighbours(&allomorphs[*x])).collect();
+                let conditions = shared_conditions(&neighbours(&allomorphs[*i]));
+                (!conditions.is_empty() && excludes(&conditions, &others)).then(|| (n, distinguishing(conditions, &others)))
+            });
+            match found
+            {
+                Some((n, conditions)) =>
+                {
+                    allomorphs[left[n]].environment = Environment::Conditioned(conditions);
+                    order.push(left.remove(n));
+                },
+                None => break,
+            }
+        }
+        if left.len() == 1
+        {
+            allomorphs[left[0]].environment = Environment::Elsewhere;
+        }
+        order.extend(left);
+
+        let allomorphs: Vec<Allomorph> = order.into_iter().map(|i| allomorphs[i].clone()).collect();
+        let underlying = allomorphs.iter().position(|x| x.environment == Environment::Elsewhere)
+          .or_else(|| (0..allomorphs.len()).rev().max_by_key(|i| allomorphs[*i].stems.len()));
+        AlternationAnalysis {position, allomorphs, underlying, unanalyzed}
+    }
+
+
+    #[cfg(test)]
+    mod alternation_tests
+    {
+        use super::*;
+        use crate::grapheme::transcription::transcription::parse_transcription;
+
+        fn analyze(pairs: &[(&str, &str)]) -> AlternationAnalysis
+        {
+            let pairs: Vec<(Vec<Segment>, Vec<Segment>)> = pairs.iter().map(|(x, y)| (parse_transcription(x), parse_transcription(y))).collect();
+            analyze_alternation(&pairs)
+        }
+
+        #[test]
+        fn analyze_alternation_test_english_plural()
+        {
+            let analysis = analyze(&[ ("kæt", "kæts"), ("dɔg", "dɔgz"), ("bʌs", "bʌsɪz"), ("kʌp", "kʌps")
+                                    , ("bi", "biz"), ("mæt͡ʃ", "mæt͡ʃɪz"), ("roʊz", "roʊzɪz"), ("bæg", "bægz")
+                                    , ("kʌf", "kʌfs"), ("bʊʃ", "bʊʃɪz")
+                                    ]);
+            let environments: Vec<String> = analysis.to_string().lines().map(|x| x.split(" | ").nth(1).unwrap_or(x).trim().to_string()).collect();
+            assert_eq!(environments, [ "environment"
+                                     , "after place is Alveolar or PostAlveolar, manner is Fricative or Affricate"
+                                     , "after vocal_folds is Voiceless"
+                                     , "elsewhere"
+                                     , "underlying form: /-z/"
+                                     ]);
+        }
+
+        #[test]
+        fn analyze_alternation_test_english_past()
+        {
+            let analysis = analyze(&[("wɔk", "wɔkt"), ("pleɪ", "pleɪd"), ("wɔnt", "wɔntɪd"), ("nid", "nidɪd"), ("bæn", "bænd"), ("kɪs", "kɪst")]);
+            assert_eq!(analysis.to_string().lines().nth(1), Some("-ɪd       | after place is Alveolar, manner is Plosive | wɔnt, nid"));
+            assert_eq!(analysis.to_string().lines().nth(2), Some("-t        | after vocal_folds is Voiceless             | wɔk, kɪs"));
+            let underlying = analysis.underlying_form().unwrap();
+            assert_eq!(underlying.environment, Environment::Elsewhere);
+            assert_eq!(construct_segments_transcription(&underlying.form), "d");
+        }
+
+        #[test]
+        fn analyze_alternation_test_prefix_and_unanalyzed()
+        {
+            let analysis = analyze(&[("pat", "impat"), ("kan", "iŋkan"), ("tal", "intal"), ("go", "went")]);
+            assert_eq!(analysis.position, AffixPosition::Prefix);
+            assert_eq!(analysis.to_string(), "allomorph | environment              | stems\n\
+                                              im-       | before place is Bilabial | pat\n\
+                                              iŋ-       | before place is Velar    | kan\n\
+                                              in-       | elsewhere                | tal\n\
+                                              underlying form: /in-/");
+            assert_eq!(analysis.unanalyzed.len(), 1);
+        }
+
+        #[test]
+        fn analyze_alternation_test_nothing_found()
+        {
+            let analysis = analyze(&[]);
+            assert_eq!(analysis.underlying_form(), None);
+            assert_eq!(analysis.to_string(), "allomorph | environment | stems\nno allomorphs found");
+            let analysis = analyze(&[("go", "went")]);
+            assert!(analysis.allomorphs.is_empty());
+            assert_eq!(analysis.unanalyzed.len(), 1);
+            assert!(analysis.to_string().ends_with("no allomorphs found"));
+        }
+    }
+}
@@ -0,0 +1,267 @@
+pub mod derivation
+{
+    use std::fmt;
+    use crate::segment::segment::Segment;
+    use crate::grapheme::transcription::transcription::construct_segments_transcription;
+    use crate::rule::rewrite::rewrite::{Rule, apply_rule};
+    use crate::table::table::text_table;
+
+    /// The forms of one word as an ordered list of rules applies to it.
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub struct Derivation
+    {
+        pub underlying: Vec<Segment>,
+
+        // The form after each rule, in the order of the rules.
+        pub forms: Vec<Vec<Segment>>
+    }
+
+    impl Derivation
+    {
+        /// The form after the last rule.
+        pub fn surface(&self) -> &[Segment]
+        {
+            self.forms.last().map(|x| x.as_slice()).unwrap_or(&self.underlying)
+        }
+
+        /// The form that the rule with this index was given.
+        pub fn input_to(&self, rule: usize) -> &[Segment]
+        {
+            if rule == 0 { &self.underlying } else { &self.forms[rule - 1] }
+        }
+
+        /// Whether the rule with this index changed the word.
+        pub fn applied(&self, rule: usize) -> bool
+        {
+            self.input_to(rule) != self.forms[rule].as_slice()
+        }
+    }
+
+    /// Applies the rules to a word in order.
+    pub fn derive(rules: &[Rule], underlying: &[Segment]) -> Derivation
+    {
+        let mut forms: Vec<Vec<Segment>> = Vec::new();
+        let mut current: Vec<Segment> = underlying.to_vec();
+        for rule in rules.iter()
+        {
+            current = apply_rule(rule, &current);
+            forms.push(current.clone());
+        }
+        Derivation {underlying: underlying.to_vec(), forms}
+    }
+
+
+    /// The derivations of several words under the same rules, shown as
+    /// a derivation table: one column per word, one row per rule,
+    /// with — where the rule does not apply.
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub struct DerivationTable
+    {
+        pub rules: Vec<Rule>,
+        pub derivations: Vec<Derivation>
+    }
+
+    /// Derives every word of a lexicon.
+    pub fn derivation_table(rules: &[Rule], lexicon: &[Vec<Segment>]) -> DerivationTable
+    {
+        DerivationTable {rules: rules.to_vec(), derivations: lexicon.iter().map(|word| derive(rules, word)).collect()}
+    }
+
+    impl fmt::Display for DerivationTable
+    {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            let mut rows: Vec<Vec<String>> = Vec::new();
+            let mut header = vec![String::from("UR")];
+            header.extend(self.derivations.iter().map(|x| format!("/{}/", construct_segments_transcription(&x.underlying))));
+            rows.push(header);
+            for (i, rule) in self.rules.iter().enumerate()
+            {
+                let mut row = vec![rule.to_string()];
+                for derivation in self.derivations.iter()
+                {
+                    row.push(if derivation.applied(i) { construct_segments_transcription(&derivation.forms[i]) } else { String::from("—") });
+                }
+                rows.push(row);
+            }
+            let mut footer = vec![String::from("SR")];
+            footer.extend(self.derivations.iter().map(|x| format!("[{}]", construct_segments_transcription(x.surface()))));
+            rows.push(footer);
+            write!(f, "{}", text_table(&rows))
+        }
+    }
+
+
+    /// How two rules can interact.
+    #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+    pub enum InteractionKind
+    {
+        // The earlier rule makes new places for the later one to apply.
+        Feeding,
+
+        // The earlier rule takes away places where the later one would apply.
+        Bleeding,
+
+        // The later rule would feed the earlier one, but comes too late.
+        Counterfeeding,
+
+        // The later rule would bleed the earlier one, but comes too late.
+        Counterbleeding
+    }
+
+    impl fmt::Display for InteractionKind
+    {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            match self
+            {
+                InteractionKind::Feeding         => write!(f, "feeding"),
+                InteractionKind::Bleeding        => write!(f, "bleeding"),
+                InteractionKind::Counterfeeding  => write!(f, "counterfeeding"),
+                InteractionKind::Counterbleeding => write!(f, "counterbleeding"),
+            }
+        }
+    }
+
+    /// An interaction between two rules, and the words that show it.
+    /// `first` is the index of the rule that comes first in the order.
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub struct Interaction
+    {
+        pub kind: InteractionKind,
+        pub first: usize,
+        pub second: usize,
+        pub words: Vec<Vec<Segment>>
+    }
+
+    impl fmt::Display for Interaction
+    {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            let words: Vec<String> = self.words.iter().map(|x| format!("/{}/", construct_segments_transcription(x))).collect();
+            write!(f, "rules {} and {}: {} ({})", self.first + 1, self.second + 1, self.kind, words.join(", "))
+        }
+    }
+
+    // Whether applying `earlier` before `later` to a form feeds or
+    // bleeds `later`, compared with `later` on the form as it is.
+    fn effect(earlier: &Rule, later: &Rule, form: &[Segment]) -> Option<InteractionKind>
+    {
+        let after_earlier = apply_rule(earlier, form);
+        if after_earlier == form
+        {
+            return None;
+        }
+        let applies_before = apply_rule(later, form) != form;
+        let applies_after = apply_rule(later, &after_earlier) != after_earlier;
+        match (applies_before, applies_after)
+        {
+            (false, true) => Some(InteractionKind::Feeding),
+            (true, false) => Some(InteractionKind::Bleeding),
+            _ => None,
+        }
+    }
+
+    /// Finds which pairs of rules feed, bleed, counterfeed or
+    /// counterbleed each other in the derivations of a test lexicon.
+    /// For each word and pair, both rules are tried on the form that
+    /// reaches the first of them: in their order for feeding and
+    /// bleeding, and in the opposite order for the counter relations.
+    pub fn rule_interactions(rules: &[Rule], lexicon: &[Vec<Segment>]) -> Vec<Interaction>
+    {
+        let mut interactions: Vec<Interaction> = Vec::new();
+        for word in lexicon.iter()
+        {
+            let derivation = derive(rules, word);
+            for first in 0..rules.len()
+            {
+                for second in first + 1..rules.len()
+                {
+                    let form = derivation.input_to(first);
+                    let mut kinds: Vec<InteractionKind> = Vec::new();
+                    kinds.extend(effect(&rules[first], &rules[second], form));
+                    kinds.extend(effect(&rules[second], &rules[first], form).map(|kind| match kind
+                    {
+                        InteractionKind::Feeding => InteractionKind::Counterfeeding,
+                        _                        => InteractionKind::Counterbleeding,
+                    }));
+                    for kind in kinds
+                    {
+                        match interactions.iter_mut().find(|x| x.kind == kind && x.first == first && x.second == second)
+                        {
+                            Some(interaction) => interaction.words.push(word.clone()),
+                            None => interactions.push(Interaction {kind, first, second, words: vec![word.clone()]}),
+                        }
+                    }
+                }
+            }
+        }
+        interactions.sort_by_key(|x| (x.first, x.second));
+        interactions
+    }
+
+    /// The interactions as a text table, with the rules written out.
+    pub fn interaction_table(rules: &[Rule], interactions: &[Interaction]) -> String
+    {
+        let mut rows: Vec<Vec<String>> = vec![vec![String::from("first rule"), String::from("second rule"), String::from("relation"), String::from("words")]];
+        for interaction in interactions.iter()
+        {
+            let words: Vec<String> = interaction.words.iter().map(|x| format!("/{}/", construct_segments_transcription(x))).collect();
+            rows.push(vec![rules[interaction.first].to_string(), rules[interaction.second].to_string(), interaction.kind.to_string(), words.join(", ")]);
+        }
+        text_table(&rows)
+    }
+
+
+    #[cfg(test)]
+    mod derivation_tests
+    {
+        use super::*;
+        use crate::rule::rewrite::rewrite::parse_rule;
+        use crate::grapheme::transcription::transcription::parse_transcription;
+
+        fn rules(texts: &[&str]) -> Vec<Rule>
+        {
+            texts.iter().map(|x| parse_rule(x).unwrap()).collect()
+        }
+
+        fn lexicon(words: &[&str]) -> Vec<Vec<Segment>>
+        {
+            words.iter().map(|x| parse_transcription(x)).collect()
+        }
+
+        #[test]
+        fn derivation_table_test()
+        {
+            let rules = rules(&["e -> 0 / _ #", "[-son] -> [-voice] / _ #"]);
+            let table = derivation_table(&rules, &lexicon(&["bade", "bad"]));
+            assert_eq!(table.derivations[0].surface(), parse_transcription("bat").as_slice());
+            let rows: Vec<Vec<String>> = table.to_string().lines().map(|line| line.split(" | ").map(|x| String::from(x.trim())).collect()).collect();
+            assert_eq!(rows[0], ["UR", "/bade/", "/bad/"]);
+            assert_eq!(rows[1], ["e → ∅ / _ #", "bad", "—"]);
+            assert_eq!(rows[2], ["[-son] → [-voice] / _ #", "bat", "bat"]);
+            assert_eq!(rows[3], ["SR", "[bat]", "[bat]"]);
+        }
+
+        #[test]
+        fn rule_interactions_test()
+        {
+            let deletion = "e -> 0 / _ #";
+            let devoicing = "[-son] -> [-voice] / _ #";
+            let feeding = rule_interactions(&rules(&[deletion, devoicing]), &lexicon(&["bade"]));
+            assert_eq!(feeding.iter().map(|x| x.kind).collect::<Vec<InteractionKind>>(), [InteractionKind::Feeding]);
+            let counterfeeding = rule_interactions(&rules(&[devoicing, deletion]), &lexicon(&["bade"]));
+            assert_eq!(counterfeeding[0].to_string(), "rules 1 and 2: counterfeeding (/bade/)");
+        }
+
+        #[test]
+        fn rule_interactions_test_bleeding()
+        {
+            let rules = rules(&["e -> 0 / _ #", "t -> s / _ e"]);
+            let interactions = rule_interactions(&rules, &lexicon(&["bate", "bata"]));
+            assert_eq!(interactions.len(), 1);
+            assert_eq!(interactions[0].kind, InteractionKind::Bleeding);
+            assert!(interaction_table(&rules, &interactions).contains("e → ∅ / _ # | t → s / _ e | bleeding | /bate/"));
+        }
+    }
+}
@@ -0,0 +1,289 @@
+pub mod features
+{
+    use std::fmt;
+    use crate::lib::{Phonet, Phonet::*, VocalFolds, VocalFolds::*, Place, Place::*, Height::*, Rounding::*,
+                     Manner, Manner::{Plosive, Trill, TapOrFlap, Approximant, Fricative, Affricate, LateralFricative, LateralApproximant, LateralFlap, UnmarkedManner},
+                     Backness, Backness::{Front, UnmarkedBackness},
+                     PlaceGroup, place_groups, voiced_phonet, devoiced_phonet, aspirated_phonet, deaspirated_phonet};
+    use BinaryFeature::*;
+
+    /// The binary (+ or −) features of generative phonology,
+    /// worked out from the place, manner and voicing of a consonant,
+    /// or the height, backness and rounding of a vowel.
+    #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+    pub enum BinaryFeature
+    {
+        Syllabic, Consonantal, Sonorant, Continuant, DelayedRelease,
+        Nasal, Lateral, Voice, SpreadGlottis,
+        Labial, Coronal, Anterior, Dorsal,
+        High, Low, Back, Round
+    }
+
+    pub static BINARY_FEATURE_STATES: [BinaryFeature; 17] =
+      [ Syllabic, Consonantal, Sonorant, Continuant, DelayedRelease
+      , Nasal, Lateral, Voice, SpreadGlottis
+      , Labial, Coronal, Anterior, Dorsal
+      , High, Low, Back, Round
+      ];
+
+    impl BinaryFeature
+    {
+        /// The short name used in rules, such as "son" for Sonorant.
+        pub fn abbreviation(self) -> &'static str
+        {
+            match self
+            {
+                Syllabic       => "syl",
+                Consonantal    => "cons",
+                Sonorant       => "son",
+                Continuant     => "cont",
+                DelayedRelease => "delrel",
+                Nasal          => "nasal",
+                Lateral        => "lat",
+                Voice          => "voice",
+                SpreadGlottis  => "sg",
+                Labial         => "lab",
+                Coronal        => "cor",
+                Anterior       => "ant",
+                Dorsal         => "dor",
+                High           => "high",
+                Low            => "low",
+                Back           => "back",
+                Round          => "round",
+            }
+        }
+
+        /// Looks a feature up by its full name or its abbreviation,
+        /// ignoring case (for example "sonorant" or "son").
+        pub fn named(name: &str) -> Option<BinaryFeature>
+        {
+            let name = name.to_lowercase();
+            let full = |feature: BinaryFeature| format!("{:?}", feature).to_lowercase();
+            BINARY_FEATURE_STATES.iter().copied().find(|feature| feature.abbreviation() == name || full(*feature) == name)
+        }
+    }
+
+    impl fmt::Display for BinaryFeature
+    {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            write!(f, "{}", self.abbreviation())
+        }
+    }
+
+
+    /// A feature with a value, such as −voice.
+    #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+    pub struct FeatureSpecification
+    {
+        pub feature: BinaryFeature,
+        pub value: bool
+    }
+
+    impl fmt::Display for FeatureSpecification
+    {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            write!(f, "{}{}", if self.value { "+" } else { "-" }, self.feature)
+        }
+    }
+
+
+    fn in_group(place: Place, group: PlaceGroup) -> Option<bool>
+    {
+        if place == UnmarkedPlace { None } else { Some(place_groups(place).contains(&group)) }
+    }
+
+    /// The value of a binary feature for a phonet. Returns None
+    /// if the phonet does not say (for example the voicing of
+    /// a consonant with unmarked vocal folds), or the feature is not
+    /// defined for it (such as Dorsal for a vowel).
+    pub fn binary_feature_value(phonet: Phonet, feature: BinaryFeature) -> Option<bool>
+    {
+        match phonet
+        {
+            Consonant {vocal_folds, place, manner, ..} =>
+            {
+                let manner = if manner == UnmarkedManner { None } else { Some(manner) };
+                let known_place = if place == UnmarkedPlace { None } else { Some(place) };
+                let voice = match vocal_folds
+                {
+                    Voiced | VoicedAspirated | CreakyVoiced => Some(true),
+                    Voiceless | VoicelessAspirated          => Some(false),
+                    UnmarkedVocalFolds                      => None,
+                };
+                match feature
+                {
+                    Syllabic       => Some(false),
+                    Consonantal    => Some(place != Glottal && manner? != Approximant),
+                    Sonorant       => Some(matches!(manner?, Manner::Nasal | Trill | TapOrFlap | Approximant | LateralApproximant | LateralFlap)),
+                    Continuant     => Some(matches!(manner?, Fricative | LateralFricative | Approximant | LateralApproximant | Trill)),
+                    DelayedRelease => Some(manner? == Affricate),
+                    Nasal          => Some(manner? == Manner::Nasal),
+                    Lateral        => Some(matches!(manner?, LateralFricative | LateralApproximant | LateralFlap | Manner::Lateral)),
+                    Voice          => voice,
+                    SpreadGlottis  => vocal_folds_known(vocal_folds).map(|_| vocal_folds == VoicelessAspirated || vocal_folds == VoicedAspirated),
+                    Labial         => in_group(place, PlaceGroup::Labial),
+                    Coronal        => in_group(place, PlaceGroup::Coronal),
+                    Dorsal         => in_group(place, PlaceGroup::Dorsal),
+                    Anterior       => Some(matches!(known_place?, Bilabial | LabioDental | Dental | Alveolar)),
+                    High           => Some(matches!(known_place?, Palatal | Velar | AlveoloPalatal | LabialVelar | LabialPalatal)),
+                    Low            => Some(matches!(known_place?, Pharyngeal | Epiglottal | Glottal)),
+                    Back           => Some(matches!(known_place?, Velar | Uvular | LabialVelar)),
+                    Round          => Some(matches!(known_place?, LabialVelar | LabialPalatal)),
+                }
+            },
+            Vowel {height, backness, rounding, vocal_folds} => match feature
+            {
+                Syllabic | Sonorant | Continuant => Some(true),
+                Consonantal | DelayedRelease | Nasal | Lateral => Some(false),
+                Voice          => vocal_folds_known(vocal_folds).map(|_| vocal_folds != Voiceless),
+                SpreadGlottis  => Some(false),
+                High           => if height == UnmarkedHeight { None } else { Some(height == Close || height == NearClose) },
+                Low            => if height == UnmarkedHeight { None } else { Some(height == Open || height == NearOpen) },
+                Back           => if backness == UnmarkedBackness { None } else { Some(backness != Front) },
+                Round          => if rounding == UnmarkedRounding { None } else { Some(rounding == Rounded) },
+                Labial | Coronal | Anterior | Dorsal => None,
+            },
+        }
+    }
+
+    fn vocal_folds_known(vocal_folds: VocalFolds) -> Option<VocalFolds>
+    {
+        if vocal_folds == UnmarkedVocalFolds { None } else { Some(vocal_folds) }
+    }
+
+    /// Whether a phonet has every one of the feature values.
+    /// A feature the phonet does not say anything about does not match.
+    pub fn matches_features(phonet: Phonet, specifications: &[FeatureSpecification]) -> bool
+    {
+        specifications.iter().all(|x| binary_feature_value(phonet, x.feature) == Some(x.value))
+    }
+
+    // The phonet that a change to one feature gives, which may
+    // still not have the value (it is checked afterwards).
+    fn changed_phonet(phonet: Phonet, feature: BinaryFeature, value: bool) -> Option<Phonet>
+    {
+        match (phonet, feature, value)
+        {
+            (_, Voice, true)          => Some(voiced_phonet(phonet)),
+            (_, Voice, false)         => Some(devoiced_phonet(phonet)),
+            (_, SpreadGlottis, true)  => Some(aspirated_phonet(phonet)),
+            (_, SpreadGlottis, false) => Some(deaspirated_phonet(phonet)),
+
+            (Consonant {vocal_folds, place, manner, airstream}, _, _) =>
+            {
+                let manner = match (feature, value, manner)
+                {
+                    (Continuant, true, Plosive) | (Continuant, true, Affricate) => Fricative,
+                    (Continuant, false, Fricative) | (Continuant, false, Approximant) => Plosive,
+                    (Continuant, false, LateralFricative) | (Continuant, false, LateralApproximant) => Plosive,
+                    (DelayedRelease, true, Plosive) | (DelayedRelease, true, Fricative) => Affricate,
+                    (DelayedRelease, false, Affricate) => Plosive,
+                    (Nasal, true, _)  => Manner::Nasal,
+                    (Nasal, false, Manner::Nasal) => Plosive,
+                    (Lateral, true, Fricative) => LateralFricative,
+                    (Lateral, true, Approximant) => LateralApproximant,
+                    (Lateral, true, TapOrFlap) => LateralFlap,
+                    (Lateral, false, LateralFricative) => Fricative,
+                    (Lateral, false, LateralApproximant) => Approximant,
+                    (Lateral, false, LateralFlap) => TapOrFlap,
+                    _ => manner,
+                };
+                let place = match (feature, value, place)
+                {
+                    (Labial, true, _)  => Bilabial,
+                    (Coronal, true, _) => Alveolar,
+                    (Dorsal, true, _)  => Velar,
+                    (Anterior, true, PostAlveolar) | (Anterior, true, Retroflex) | (Anterior, true, PalatoAlveolar) => Alveolar,
+                    (Anterior, false, Dental) | (Anterior, false, Alveolar) => PostAlveolar,
+                    (Back, true, Palatal) => Velar,
+                    (Back, false, Velar) | (Back, false, Uvular) => Palatal,
+                    (High, false, Velar) => Uvular,
+                    (High, true, Uvular) => Velar,
+                    _ => place,
+                };
+                let vocal_folds = if feature == Nasal && value { Voiced } else { vocal_folds };
+                Some(Consonant {vocal_folds, place, manner, airstream})
+            },
+
+            (Vowel {height, backness, rounding, vocal_folds}, _, _) =>
+            {
+                let height = match (feature, value)
+                {
+                    (High, true)  => Close,
+                    (High, false) if height == Close || height == NearClose => CloseMid,
+                    (Low, true)   => Open,
+                    (Low, false) if height == Open || height == NearOpen => OpenMid,
+                    _ => height,
+                };
+                let backness = match (feature, value)
+                {
+                    (Back, true)  => Backness::Back,
+                    (Back, false) => Front,
+                    _ => backness,
+                };
+                let rounding = match (feature, value)
+                {
+                    (Round, true)  => Rounded,
+                    (Round, false) => Unrounded,
+                    _ => rounding,
+                };
+                Some(Vowel {height, backness, rounding, vocal_folds})
+            },
+        }
+    }
+
+    /// Changes a phonet so that it has a feature value, for example
+    /// +continuant turns a plosive into a fricative. Returns None
+    /// if there is no such change in this model (such as −syllabic
+    /// for a vowel).
+    pub fn with_binary_feature(phonet: Phonet, specification: FeatureSpecification) -> Option<Phonet>
+    {
+        if binary_feature_value(phonet, specification.feature) == Some(specification.value)
+        {
+            return Some(phonet);
+        }
+        changed_phonet(phonet, specification.feature, specification.value)
+          .filter(|changed| binary_feature_value(*changed, specification.feature) == Some(specification.value))
+    }
+
+    /// Changes a phonet so that it has every one of the feature values,
+    /// in order. Returns None if one of them cannot be given to it.
+    pub fn with_binary_features(phonet: Phonet, specifications: &[FeatureSpecification]) -> Option<Phonet>
+    {
+        specifications.iter().try_fold(phonet, |acc, x| with_binary_feature(acc, *x))
+    }
+
+
+    #[cfg(test)]
+    mod features_tests
+    {
+        use super::*;
+        use crate::grapheme::international_phonetic_alphabet::international_phonetic_alphabet::{analyze_transcription, construct_transcription};
+
+        fn ipa(text: &str) -> Phonet
+        {
+            analyze_transcription(String::from(text))
+        }
+
+        #[test]
+        fn binary_feature_value_test()
+        {
+            let obstruent = [FeatureSpecification {feature: Voice, value: true}, FeatureSpecification {feature: Sonorant, value: false}];
+            assert!(matches_features(ipa("d"), &obstruent));
+            assert!(!matches_features(ipa("n"), &obstruent));
+            assert_eq!(binary_feature_value(ipa("i"), High), Some(true));
+            assert_eq!(binary_feature_value(ipa("i"), Dorsal), None);
+        }
+
+        #[test]
+        fn with_binary_feature_test()
+        {
+            let continuant = FeatureSpecification {feature: Continuant, value: true};
+            assert_eq!(construct_transcription(with_binary_feature(ipa("b"), continuant).unwrap()), "β");
+            assert_eq!(with_binary_feature(ipa("a"), FeatureSpecification {feature: Syllabic, value: false}), None);
+            assert_eq!(BinaryFeature::named("Sonorant"), Some(Sonorant));
+        }
+    }
+}
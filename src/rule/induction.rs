@@ -0,0 +1,406 @@
+pub mod induction
+{
+    use std::fmt;
+    use crate::lib::{Phonet, Phonet::*, unmark_all_differences};
+    use crate::segment::segment::Segment;
+    use crate::optimality::constraint::constraint::Candidate;
+    use crate::rule::features::features::{BINARY_FEATURE_STATES, FeatureSpecification, binary_feature_value, with_binary_features};
+    use crate::rule::rewrite::rewrite::{Rule, Matcher, EnvironmentItem, Target, Change, application_sites, rewritten_segment};
+
+    /// A rule proposed to explain a set of underlying and surface forms.
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub struct InducedRule
+    {
+        pub rule: Rule,
+
+        // How many of the changes in the forms the rule makes,
+        // out of how many there are.
+        pub covered: usize,
+        pub changes: usize,
+
+        // The number of symbols, feature values and
+        // boundaries in the rule: lower is simpler.
+        pub complexity: usize
+    }
+
+    impl fmt::Display for InducedRule
+    {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            write!(f, "{} (covers {} of {} changes, complexity {})", self.rule, self.covered, self.changes, self.complexity)
+        }
+    }
+
+
+    // One segment that changed: the segment, what it became
+    // (None if it was deleted), and its neighbours in the
+    // underlying form (None at the edge of the word).
+    #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+    struct Observation
+    {
+        before: Phonet,
+        after: Option<Phonet>,
+        left: Option<Phonet>,
+        right: Option<Phonet>
+    }
+
+    // The segments of an underlying form that changed, with what they
+    // became. Forms of the same length are compared position by
+    // position, others are aligned first. Inserted segments are left out.
+    fn observed_changes(underlying: &[Segment], surface: &[Segment]) -> Vec<(usize, Option<Phonet>)>
+    {
+        let correspondence: Vec<Option<usize>> = if underlying.len() == surface.len()
+        {
+            (0..underlying.len()).map(Some).collect()
+        }
+        else
+        {
+            Candidate::aligned(underlying, surface).correspondence
+        };
+        correspondence.iter().enumerate().filter_map(|(i, j)| match j
+        {
+            None                                                    => Some((i, None)),
+            Some(j) if surface[*j].phonet != underlying[i].phonet   => Some((i, Some(surface[*j].phonet))),
+            _                                                       => None,
+        }).collect()
+    }
+
+    // The feature values that turn one phonet into the other, if there are any.
+    fn feature_change(before: Phonet, after: Phonet) -> Option<Vec<FeatureSpecification>>
+    {
+        let specifications: Vec<FeatureSpecification> = BINARY_FEATURE_STATES.iter().filter_map(|feature|
+        {
+            let value = binary_feature_value(after, *feature)?;
+            if binary_feature_value(before, *feature) == Some(value) { None } else { Some(FeatureSpecification {feature: *feature, value}) }
+        }).collect();
+        if !specifications.is_empty() && with_binary_features(before, &specifications) == Some(after) { Some(specifications) } else { None }
+    }
+
+    // The observations grouped by what happened to the segments: a deletion,
+    // or a change of one symbol into another (with the symbols of a group
+    // each changing in only one way). A change that is also a change of
+    // features goes into a group for those features as well.
+    fn changes_by_kind(observations: &[Observation]) -> Vec<(Change, Vec<Observation>)>
+    {
+        let mut groups: Vec<(Change, Vec<Observation>)> = Vec::new();
+        for observation in observations.iter()
+        {
+            let kinds = match observation.after
+            {
+                None        => vec![Change::Deletion],
+                Some(after) => match feature_change(observation.before, after)
+                {
+                    Some(specifications) => vec![Change::Phonets(Vec::new()), Change::Features(specifications)],
+                    None                 => vec![Change::Phonets(Vec::new())],
+                },
+            };
+            for change in kinds
+            {
+                let compatible = groups.iter().position(|(kind, group)| match (kind, &change)
+                {
+                    (Change::Phonets(_), Change::Phonets(_)) =>
+                      group.iter().all(|x| x.before != observation.before || x.after == observation.after),
+                    _ => *kind == change,
+                });
+                match compatible
+                {
+                    Some(i) => groups[i].1.push(*observation),
+                    None    => groups.push((change, vec![*observation])),
+                }
+            }
+        }
+        groups
+    }
+
+    // The feature values that a set of phonets share, as worked out from
+    // their generalization (each feature that varies among them unmarked).
+    // None if they mix consonants and vowels, or share nothing.
+    fn shared_features(phonets: &[Phonet]) -> Option<Vec<FeatureSpecification>>
+    {
+        let generalization = unmark_all_differences(phonets)?;
+        if generalization.mixes_consonants_and_vowels
+        {
+            return None;
+        }
+        let specifications: Vec<FeatureSpecification> = BINARY_FEATURE_STATES.iter()
+          .filter_map(|feature| binary_feature_value(generalization.phonet, *feature).map(|value| FeatureSpecification {feature: *feature, value}))
+          .filter(|x| phonets.iter().all(|phonet| binary_feature_value(*phonet, x.feature) == Some(x.value)))
+          .collect();
+        if specifications.is_empty() { None } else { Some(specifications) }
+    }
+
+    fn distinct(phonets: impl Iterator<Item = Phonet>) -> Vec<Phonet>
+    {
+        let mut distinct: Vec<Phonet> = Vec::new();
+        for phonet in phonets
+        {
+            if !distinct.contains(&phonet)
+            {
+                distinct.push(phonet);
+            }
+        }
+        distinct
+    }
+
+    // The matchers for a set of phonets: the phonets themselves,
+    // then the more general ones (a feature bundle, C or V).
+    fn matchers(phonets: &[Phonet]) -> Vec<Matcher>
+    {
+        let mut matchers = vec![if phonets.len() == 1 { Matcher::Phonet(phonets[0]) } else { Matcher::Set(phonets.to_vec()) }];
+        if let Some(specifications) = shared_features(phonets)
+        {
+            matchers.push(Matcher::Features(specifications));
+        }
+        if phonets.iter().all(|x| matches!(x, Consonant {..}))
+        {
+            matchers.push(Matcher::Consonant);
+        }
+        if phonets.iter().all(|x| matches!(x, Vowel {..}))
+        {
+            matchers.push(Matcher::Vowel);
+        }
+        matchers
+    }
+
+    // The environments on one side of the changed segments:
+    // none at all, the edge of the word, or one segment.
+    fn environments(neighbours: &[Option<Phonet>]) -> Vec<Vec<EnvironmentItem>>
+    {
+        let mut environments: Vec<Vec<EnvironmentItem>> = vec![Vec::new()];
+        if neighbours.iter().all(|x| x.is_none())
+        {
+            environments.push(vec![EnvironmentItem::Boundary]);
+        }
+        else if neighbours.iter().all(|x| x.is_some())
+        {
+            let phonets = distinct(neighbours.iter().flatten().copied());
+            environments.extend(matchers(&phonets).into_iter().map(|x| vec![EnvironmentItem::Segment(x)]));
+        }
+        environments
+    }
+
+    // The targets and changes for a group of observations. A change of
+    // features can apply to any segment with the shared features; a change
+    // of symbols needs each symbol listed with what it becomes.
+    fn targets(change: &Change, group: &[Observation]) -> Vec<(Target, Change)>
+    {
+        let befores = distinct(group.iter().map(|x| x.before));
+        match change
+        {
+            Change::Phonets(_) =>
+            {
+                let afters: Vec<Phonet> = befores.iter()
+                  .filter_map(|before| group.iter().find(|x| x.before == *before).and_then(|x| x.after)).collect();
+                vec![(Target::Segments(befores.into_iter().map(Matcher::Phonet).collect()), Change::Phonets(afters))]
+            },
+            _ => matchers(&befores).into_iter().map(|x| (Target::Segments(vec![x]), change.clone())).collect(),
+        }
+    }
+
+
+    // How many of the observed changes a rule makes, and how many
+    // segments it changes wrongly (including ones that stay the same).
+    fn score(rule: &Rule, pairs: &[(Vec<Segment>, Vec<Segment>)], observed: &[Vec<(usize, Option<Phonet>)>]) -> (usize, usize)
+    {
+        let (mut covered, mut errors) = (0, 0);
+        for ((underlying, _), changes) in pairs.iter().zip(observed.iter())
+        {
+            for site in application_sites(rule, underlying)
+            {
+                let predicted = rewritten_segment(rule, underlying[site]).map(|x| x.phonet);
+                if predicted == Some(underlying[site].phonet)
+                {
+                    continue;
+                }
+                let actual = changes.iter().find(|(i, _)| *i == site).map(|(_, after)| *after).unwrap_or(Some(underlying[site].phonet));
+                if predicted == actual { covered += 1 } else { errors += 1 }
+            }
+        }
+        (covered, errors)
+    }
+
+    // The rule with the n-th feature value left out of its bundles,
+    // counting only bundles with more than one value.
+    fn without_specification(rule: &Rule, mut n: usize) -> Option<Rule>
+    {
+        let mut rule = rule.clone();
+        let mut removed = false;
+        let mut bundles: Vec<&mut Matcher> = Vec::new();
+        if let Target::Segments(matchers) = &mut rule.target
+        {
+            bundles.extend(matchers.iter_mut());
+        }
+        for item in rule.left.iter_mut().chain(rule.right.iter_mut())
+        {
+            if let EnvironmentItem::Segment(matcher) = item
+            {
+                bundles.push(matcher);
+            }
+        }
+        for matcher in bundles
+        {
+            if let Matcher::Features(specifications) = matcher
+            {
+                if specifications.len() > 1
+                {
+                    if n < specifications.len()
+                    {
+                        specifications.remove(n);
+                        removed = true;
+                        break;
+                    }
+                    n -= specifications.len();
+                }
+            }
+        }
+        if removed { Some(rule) } else { None }
+    }
+
+    // Leaves out each feature value that the rule does without:
+    // one whose absence neither loses a change nor makes a wrong one.
+    fn most_general(rule: Rule, pairs: &[(Vec<Segment>, Vec<Segment>)], observed: &[Vec<(usize, Option<Phonet>)>]) -> Rule
+    {
+        let (covered, _) = score(&rule, pairs, observed);
+        let mut rule = rule;
+        let mut n = 0;
+        while let Some(general) = without_specification(&rule, n)
+        {
+            let (general_covered, errors) = score(&general, pairs, observed);
+            if errors == 0 && general_covered >= covered { rule = general } else { n += 1 }
+        }
+        rule
+    }
+
+    fn matcher_complexity(matcher: &Matcher) -> usize
+    {
+        match matcher
+        {
+            Matcher::Set(phonets)            => phonets.len(),
+            Matcher::Features(specification) => specification.len(),
+            _                                => 1,
+        }
+    }
+
+    fn complexity(rule: &Rule) -> usize
+    {
+        let target = match &rule.target
+        {
+            Target::Segments(matchers) => matchers.iter().map(matcher_complexity).sum(),
+            Target::Nothing            => 1,
+        };
+        let change = match &rule.change
+        {
+            Change::Deletion                 => 1,
+            Change::Phonets(phonets)         => phonets.len(),
+            Change::Features(specifications) => specifications.len(),
+        };
+        let environment: usize = rule.left.iter().chain(rule.right.iter()).map(|item| match item
+        {
+            EnvironmentItem::Segment(matcher) => matcher_complexity(matcher),
+            _                                 => 1,
+        }).sum();
+        target + change + environment
+    }
+
+    // The number of symbols that the environment lists one by one, so that
+    // of two rules as simple as each other, the one with classes comes first.
+    fn listed_symbols(rule: &Rule) -> usize
+    {
+        rule.left.iter().chain(rule.right.iter()).map(|item| match item
+        {
+            EnvironmentItem::Segment(Matcher::Phonet(_)) => 1,
+            EnvironmentItem::Segment(Matcher::Set(x))    => x.len(),
+            _                                            => 0,
+        }).sum()
+    }
+
+    /// Proposes rewrite rules for pairs of underlying and surface forms.
+    /// The changed segments are grouped by what happened to them, and for
+    /// each group the targets and the segments next to them are generalized
+    /// as far as the data allow: a rule may not change a segment that stays
+    /// the same, or change one wrongly. The rules are ranked by how many of
+    /// the changes they make, then by how simple they are. Insertions are
+    /// not looked at.
+    pub fn induce_rules(pairs: &[(Vec<Segment>, Vec<Segment>)]) -> Vec<InducedRule>
+    {
+        let observed: Vec<Vec<(usize, Option<Phonet>)>> = pairs.iter().map(|(underlying, surface)| observed_changes(underlying, surface)).collect();
+        let changes: usize = observed.iter().map(|x| x.len()).sum();
+        let mut observations: Vec<Observation> = Vec::new();
+        for ((underlying, _), changed) in pairs.iter().zip(observed.iter())
+        {
+            for (i, after) in changed.iter()
+            {
+                observations.push(Observation { before: underlying[*i].phonet
+                                              , after: *after
+                                              , left: if *i == 0 { None } else { Some(underlying[i - 1].phonet) }
+                                              , right: underlying.get(i + 1).map(|x| x.phonet)
+                                              });
+            }
+        }
+
+        let mut induced: Vec<InducedRule> = Vec::new();
+        for (change, group) in changes_by_kind(&observations)
+        {
+            let lefts: Vec<Option<Phonet>> = group.iter().map(|x| x.left).collect();
+            let rights: Vec<Option<Phonet>> = group.iter().map(|x| x.right).collect();
+            for (target, change) in targets(&change, &group)
+            {
+                for left in environments(&lefts)
+                {
+                    for right in environments(&rights)
+                    {
+                        let rule = Rule {target: target.clone(), change: change.clone(), left: left.clone(), right};
+                        if score(&rule, pairs, &observed).1 > 0
+                        {
+                            continue;
+                        }
+                        let rule = most_general(rule, pairs, &observed);
+                        let (covered, _) = score(&rule, pairs, &observed);
+                        if covered > 0 && !induced.iter().any(|x| x.rule == rule)
+                        {
+                            induced.push(InducedRule {complexity: complexity(&rule), rule, covered, changes});
+                        }
+                    }
+                }
+            }
+        }
+        induced.sort_by_key(|x| (std::cmp::Reverse(x.covered), x.complexity, listed_symbols(&x.rule), x.rule.to_string()));
+        induced
+    }
+
+
+    #[cfg(test)]
+    mod induction_tests
+    {
+        use super::*;
+        use crate::grapheme::transcription::transcription::parse_transcription;
+
+        fn induce(pairs: &[(&str, &str)]) -> Vec<String>
+        {
+            let pairs: Vec<(Vec<Segment>, Vec<Segment>)> = pairs.iter().map(|(u, s)| (parse_transcription(u), parse_transcription(s))).collect();
+            induce_rules(&pairs).iter().map(|x| x.to_string()).collect()
+        }
+
+        #[test]
+        fn induce_rules_test_final_devoicing()
+        {
+            let rules = induce(&[("ab", "ap"), ("bad", "bat"), ("rog", "rok"), ("bada", "bada"), ("bar", "bar")]);
+            assert_eq!(rules[0], "[-cont] → [-voice] / _ # (covers 3 of 3 changes, complexity 3)");
+            assert!(rules.iter().all(|x| !x.starts_with("C ")));
+        }
+
+        #[test]
+        fn induce_rules_test_spirantization()
+        {
+            let rules = induce(&[("apa", "afa"), ("ata", "aθa"), ("aka", "axa"), ("pat", "pat")]);
+            assert_eq!(rules[0], "p t k → f θ x / V _ V (covers 3 of 3 changes, complexity 8)");
+        }
+
+        #[test]
+        fn induce_rules_test_deletion()
+        {
+            let rules = induce(&[("kate", "kat"), ("pine", "pin"), ("eta", "eta"), ("kapi", "kapi"), ("kepa", "kepa"), ("tena", "tena")]);
+            assert_eq!(rules[0], "e → ∅ / _ # (covers 2 of 2 changes, complexity 3)");
+        }
+    }
+}
@@ -0,0 +1,8 @@
+pub mod alternation;
+pub mod derivation;
+pub mod features;
+pub mod induction;
+pub mod reverse;
+pub mod rewrite;
+pub mod sound_change;
+pub mod transducer;
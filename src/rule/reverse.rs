@@ -0,0 +1,310 @@
+pub mod reverse
+{
+    use crate::lib::{Phonet, Phonet::*, Feature, FeatureValue, with_feature_value, generate_from_unmarked, impossible,
+                     VocalFolds::*, Place::*, Manner::*, Airstream::*, Height::*, Backness::*, Rounding::*};
+    use crate::segment::segment::Segment;
+    use crate::grapheme::international_phonetic_alphabet::international_phonetic_alphabet::can_transcribe;
+    use crate::rule::features::features::{BinaryFeature, with_binary_features};
+    use crate::rule::rewrite::rewrite::{Rule, Matcher, Target, Change, apply_rule};
+
+    /// How far reverse application may go.
+    #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+    pub struct ReverseOptions
+    {
+        // The most underlying forms to list.
+        pub max_forms: usize,
+
+        // The most forms to try, for each rule and each form it is undone
+        // on, before giving up (every one is checked by applying the rule).
+        pub max_candidates: usize
+    }
+
+    impl Default for ReverseOptions
+    {
+        fn default() -> ReverseOptions
+        {
+            ReverseOptions {max_forms: 100, max_candidates: 100_000}
+        }
+    }
+
+    /// The underlying forms found, and whether a limit was reached
+    /// (so that there may be more).
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub struct UnderlyingForms
+    {
+        pub forms: Vec<Vec<Segment>>,
+        pub truncated: bool
+    }
+
+
+    fn unmarked(phonet: Phonet, feature: Feature) -> Phonet
+    {
+        let value = match feature
+        {
+            Feature::VocalFolds => FeatureValue::VocalFolds(UnmarkedVocalFolds),
+            Feature::Place      => FeatureValue::Place(UnmarkedPlace),
+            Feature::Manner     => FeatureValue::Manner(UnmarkedManner),
+            Feature::Airstream  => FeatureValue::Airstream(UnmarkedAirstream),
+            Feature::Height     => FeatureValue::Height(UnmarkedHeight),
+            Feature::Backness   => FeatureValue::Backness(UnmarkedBackness),
+            Feature::Rounding   => FeatureValue::Rounding(UnmarkedRounding),
+        };
+        with_feature_value(phonet, value).unwrap_or(phonet)
+    }
+
+    // The features of the model that a binary feature is worked out from.
+    fn model_features(feature: BinaryFeature) -> &'static [Feature]
+    {
+        match feature
+        {
+            BinaryFeature::Syllabic => &[],
+            BinaryFeature::Consonantal | BinaryFeature::Sonorant | BinaryFeature::Continuant
+              | BinaryFeature::DelayedRelease | BinaryFeature::Nasal | BinaryFeature::Lateral => &[Feature::Manner],
+            BinaryFeature::Voice | BinaryFeature::SpreadGlottis => &[Feature::VocalFolds],
+            BinaryFeature::Labial | BinaryFeature::Coronal | BinaryFeature::Dorsal | BinaryFeature::Anterior => &[Feature::Place],
+            BinaryFeature::High | BinaryFeature::Low => &[Feature::Height, Feature::Place],
+            BinaryFeature::Back  => &[Feature::Backness, Feature::Place],
+            BinaryFeature::Round => &[Feature::Rounding, Feature::Place],
+        }
+    }
+
+    fn any_consonant() -> Phonet
+    {
+        Consonant {vocal_folds: UnmarkedVocalFolds, place: UnmarkedPlace, manner: UnmarkedManner, airstream: PulmonicEgressive}
+    }
+
+    fn any_vowel() -> Phonet
+    {
+        Vowel {height: UnmarkedHeight, backness: UnmarkedBackness, rounding: UnmarkedRounding, vocal_folds: Voiced}
+    }
+
+    // The phonets that a matcher can match (that can be written in IPA).
+    fn matched_phonets(matcher: &Matcher) -> Vec<Phonet>
+    {
+        match matcher
+        {
+            Matcher::Phonet(phonet) => vec![*phonet],
+            Matcher::Set(phonets)   => phonets.clone(),
+            Matcher::Consonant      => usable(generate_from_unmarked(any_consonant())),
+            Matcher::Vowel          => usable(generate_from_unmarked(any_vowel())),
+            Matcher::Features(_)    =>
+            {
+                let mut phonets = usable(generate_from_unmarked(any_consonant()));
+                phonets.extend(usable(generate_from_unmarked(any_vowel())));
+                phonets.into_iter().filter(|x| matcher.matches(Segment::new(*x))).collect()
+            },
+        }
+    }
+
+    fn usable(phonets: Vec<Phonet>) -> Vec<Phonet>
+    {
+        phonets.into_iter().filter(|x| !impossible(*x) && can_transcribe(*x)).collect()
+    }
+
+    // The phonets that the rule could have changed into this one.
+    fn sources(rule: &Rule, phonet: Phonet) -> Vec<Phonet>
+    {
+        let matchers = match &rule.target
+        {
+            Target::Segments(matchers) => matchers,
+            Target::Nothing            => return Vec::new(),
+        };
+        let mut sources: Vec<Phonet> = Vec::new();
+        match &rule.change
+        {
+            // The features the rule sets are neutralized: any value
+            // of the model features behind them could have been there.
+            Change::Features(specifications) =>
+            {
+                let mut neutralized = phonet;
+                for specification in specifications.iter()
+                {
+                    for feature in model_features(specification.feature)
+                    {
+                        neutralized = unmarked(neutralized, *feature);
+                    }
+                }
+                for source in usable(generate_from_unmarked(neutralized))
+                {
+                    let changes_into = with_binary_features(source, specifications).unwrap_or(source) == phonet;
+                    if source != phonet && changes_into && matchers.iter().any(|x| x.matches(Segment::new(source)))
+                    {
+                        sources.push(source);
+                    }
+                }
+            },
+            Change::Phonets(phonets) =>
+            {
+                for (i, matcher) in matchers.iter().enumerate()
+                {
+                    let result = if phonets.len() == 1 { phonets[0] } else { phonets[i] };
+                    if result == phonet
+                    {
+                        for source in matched_phonets(matcher)
+                        {
+                            if source != phonet && !sources.contains(&source)
+                            {
+                                sources.push(source);
+                            }
+                        }
+                    }
+                }
+            },
+            Change::Deletion => (),
+        }
+        sources
+    }
+
+    // The choices for each slot: the boundaries before, between and
+    // after the segments (even slots) and the segments (odd slots).
+    // None leaves a boundary empty or takes a segment out.
+    fn slots(rule: &Rule, segments: &[Segment]) -> Vec<Vec<Option<Segment>>>
+    {
+        let inserted: Option<Phonet> = match (&rule.target, &rule.change)
+        {
+            (Target::Nothing, Change::Phonets(phonets)) if phonets.len() == 1 => Some(phonets[0]),
+            _ => None,
+        };
+        let deleted: Vec<Phonet> = match (&rule.target, &rule.change)
+        {
+            (Target::Segments(matchers), Change::Deletion) => matchers.iter().flat_map(matched_phonets).collect(),
+            _ => Vec::new(),
+        };
+
+        let mut slots: Vec<Vec<Option<Segment>>> = Vec::new();
+        for i in 0..=segments.len()
+        {
+            let mut boundary: Vec<Option<Segment>> = vec![None];
+            boundary.extend(deleted.iter().map(|x| Some(Segment::new(*x))));
+            slots.push(boundary);
+            if let Some(segment) = segments.get(i)
+            {
+                let mut choices: Vec<Option<Segment>> = vec![Some(*segment)];
+                choices.extend(sources(rule, segment.phonet).into_iter().map(|phonet| Some(Segment {phonet, ..*segment})));
+                if inserted == Some(segment.phonet)
+                {
+                    choices.push(None);
+                }
+                slots.push(choices);
+            }
+        }
+        slots
+    }
+
+    /// The forms that a rule turns into the given one (the form itself
+    /// included, if the rule leaves it as it is). Changed features are
+    /// expanded with generate_from_unmarked, deleted segments are put
+    /// back, and inserted ones taken out. Insertions of more than one
+    /// segment are not undone.
+    pub fn unapply_rule(rule: &Rule, segments: &[Segment], options: &ReverseOptions) -> UnderlyingForms
+    {
+        let slots = slots(rule, segments);
+        let mut forms: Vec<Vec<Segment>> = Vec::new();
+        let mut choice = vec![0; slots.len()];
+        let mut tried = 0;
+        loop
+        {
+            let candidate: Vec<Segment> = slots.iter().zip(choice.iter()).filter_map(|(slot, i)| slot[*i]).collect();
+            if apply_rule(rule, &candidate) == segments && !forms.contains(&candidate)
+            {
+                if forms.len() == options.max_forms
+                {
+                    return UnderlyingForms {forms, truncated: true};
+                }
+                forms.push(candidate);
+            }
+            tried += 1;
+            if tried == options.max_candidates
+            {
+                return UnderlyingForms {forms, truncated: true};
+            }
+
+            // The next combination of choices, like counting.
+            let mut slot = 0;
+            while slot < slots.len() && choice[slot] + 1 == slots[slot].len()
+            {
+                choice[slot] = 0;
+                slot += 1;
+            }
+            if slot == slots.len()
+            {
+                return UnderlyingForms {forms, truncated: false};
+            }
+            choice[slot] += 1;
+        }
+    }
+
+    /// Runs a cascade of rules backwards from a surface form, and lists
+    /// every underlying form that the rules, applied in order, turn into it.
+    pub fn underlying_forms(rules: &[Rule], surface: &[Segment], options: &ReverseOptions) -> UnderlyingForms
+    {
+        let mut forms: Vec<Vec<Segment>> = vec![surface.to_vec()];
+        let mut truncated = false;
+        for rule in rules.iter().rev()
+        {
+            let mut earlier: Vec<Vec<Segment>> = Vec::new();
+            for form in forms.iter()
+            {
+                let undone = unapply_rule(rule, form, options);
+                truncated |= undone.truncated;
+                for candidate in undone.forms
+                {
+                    if earlier.len() == options.max_forms
+                    {
+                        truncated = true;
+                        break;
+                    }
+                    if !earlier.contains(&candidate)
+                    {
+                        earlier.push(candidate);
+                    }
+                }
+            }
+            forms = earlier;
+        }
+        UnderlyingForms {forms, truncated}
+    }
+
+
+    #[cfg(test)]
+    mod reverse_tests
+    {
+        use super::*;
+        use crate::rule::rewrite::rewrite::parse_rule;
+        use crate::grapheme::transcription::transcription::{parse_transcription, construct_segments_transcription};
+
+        fn underlying(rules: &[&str], surface: &str, options: &ReverseOptions) -> (Vec<String>, bool)
+        {
+            let rules: Vec<Rule> = rules.iter().map(|x| parse_rule(x).unwrap()).collect();
+            let result = underlying_forms(&rules, &parse_transcription(surface), options);
+            (result.forms.iter().map(|x| construct_segments_transcription(x)).collect(), result.truncated)
+        }
+
+        #[test]
+        fn underlying_forms_test_final_devoicing()
+        {
+            let (forms, truncated) = underlying(&["[-son] -> [-voice] / _ #"], "bat", &ReverseOptions::default());
+            assert_eq!(forms, ["bat", "bad"]);
+            assert!(!truncated);
+            let (forms, _) = underlying(&["[-son] -> [-voice] / _ #"], "bad", &ReverseOptions::default());
+            assert!(forms.is_empty());
+        }
+
+        #[test]
+        fn underlying_forms_test_cascade()
+        {
+            let rules = ["e -> 0 / _ #", "p t k -> f θ x / V _ V", "0 -> ə / C _ #"];
+            let (forms, _) = underlying(&rules, "afə", &ReverseOptions::default());
+            assert_eq!(forms, ["afə", "afəe", "apə", "apəe", "af", "afe"]);
+        }
+
+        #[test]
+        fn underlying_forms_test_limit()
+        {
+            let options = ReverseOptions {max_forms: 1, ..ReverseOptions::default()};
+            let (forms, truncated) = underlying(&["[-son] -> [-voice] / _ #"], "bat", &options);
+            assert_eq!(forms, ["bat"]);
+            assert!(truncated);
+        }
+    }
+}
@@ -0,0 +1,593 @@
+pub mod rewrite
+{
+    use std::fmt;
+    use crate::lib::{Phonet, Phonet::*};
+    use crate::segment::segment::Segment;
+    use crate::grapheme::international_phonetic_alphabet::international_phonetic_alphabet::{construct_transcription, can_transcribe};
+    use crate::grapheme::transcription::transcription::{split_graphemes, analyze_segment, parse_transcription, construct_segments_transcription};
+    use crate::rule::features::features::{BinaryFeature, FeatureSpecification, matches_features, with_binary_features};
+
+    /// Something that one segment of a word can match.
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub enum Matcher
+    {
+        Phonet(Phonet),                       // A symbol, such as p.
+        Set(Vec<Phonet>),                     // Any of several, such as {p, t, k}.
+        Features(Vec<FeatureSpecification>),  // A feature bundle, such as [+voice -son].
+        Consonant,                            // C
+        Vowel                                 // V
+    }
+
+    impl Matcher
+    {
+        /// Whether a segment matches. Only the phonet of the segment is
+        /// looked at, not its secondary articulation, length or stress.
+        pub fn matches(&self, segment: Segment) -> bool
+        {
+            match self
+            {
+                Matcher::Phonet(phonet)          => segment.phonet == *phonet,
+                Matcher::Set(phonets)            => phonets.contains(&segment.phonet),
+                Matcher::Features(specification) => matches_features(segment.phonet, specification),
+                Matcher::Consonant               => matches!(segment.phonet, Consonant {..}),
+                Matcher::Vowel                   => matches!(segment.phonet, Vowel {..}),
+            }
+        }
+    }
+
+    /// One part of the environment of a rule.
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub enum EnvironmentItem
+    {
+        Boundary,                           // #, the edge of the word.
+        Segment(Matcher),
+        Optional(Vec<EnvironmentItem>),     // (...), present or not.
+        Repeated(Box<EnvironmentItem>)      // X*, any number of times, also none.
+    }
+
+    /// What a rule applies to.
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub enum Target
+    {
+        // 0 or ∅: the rule inserts something.
+        Nothing,
+
+        // One segment, matching any of these. With several,
+        // the change says what each of them becomes.
+        Segments(Vec<Matcher>)
+    }
+
+    /// What a rule does to its target.
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub enum Change
+    {
+        // 0 or ∅: the target is deleted.
+        Deletion,
+
+        // The target becomes this symbol or, with several targets,
+        // the symbol in the same position.
+        Phonets(Vec<Phonet>),
+
+        // The target takes these feature values.
+        Features(Vec<FeatureSpecification>)
+    }
+
+    /// A rewrite rule in the style of The Sound Pattern of English:
+    /// target → change / left _ right.
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub struct Rule
+    {
+        pub target: Target,
+        pub change: Change,
+        pub left: Vec<EnvironmentItem>,
+        pub right: Vec<EnvironmentItem>
+    }
+
+
+    /// Why a rule could not be read.
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub enum RuleParseError
+    {
+        MissingArrow,
+        MissingFocus,                   // The environment has no _.
+        SeveralFoci,
+        EmptyTarget,
+        EmptyChange,
+        Unclosed(char),
+        Unexpected(char),
+        UnknownFeature(String),
+        UnknownSymbol(String),
+        NothingToRepeat,                // A * with nothing before it.
+        TargetNotASegment,              // # or ( ) or * in the target.
+        ChangeCountMismatch {targets: usize, changes: usize},
+        NothingToInsert                 // 0 → [features], or 0 → 0.
+    }
+
+    impl fmt::Display for RuleParseError
+    {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            match self
+            {
+                RuleParseError::MissingArrow        => write!(f, "the rule has no -> (or →)"),
+                RuleParseError::MissingFocus        => write!(f, "the environment has no _"),
+                RuleParseError::SeveralFoci         => write!(f, "the environment has more than one _"),
+                RuleParseError::EmptyTarget         => write!(f, "there is nothing before the arrow"),
+                RuleParseError::EmptyChange         => write!(f, "there is nothing after the arrow"),
+                RuleParseError::Unclosed(x)         => write!(f, "a {} is not closed", x),
+                RuleParseError::Unexpected(x)       => write!(f, "unexpected {}", x),
+                RuleParseError::UnknownFeature(x)   => write!(f, "unknown feature: {}", x),
+                RuleParseError::UnknownSymbol(x)    => write!(f, "unknown symbol: {}", x),
+                RuleParseError::NothingToRepeat     => write!(f, "a * has nothing before it"),
+                RuleParseError::TargetNotASegment   => write!(f, "the target can only be segments"),
+                RuleParseError::ChangeCountMismatch {targets, changes} =>
+                  write!(f, "{} targets but {} changes", targets, changes),
+                RuleParseError::NothingToInsert     => write!(f, "an insertion needs symbols to insert"),
+            }
+        }
+    }
+
+
+    static SPECIAL: [char; 11] = ['#', '(', ')', '[', ']', '{', '}', '*', ',', '_', '/'];
+
+    fn is_nothing(text: &str) -> bool
+    {
+        text == "0" || text == "∅"
+    }
+
+    fn parse_symbol(grapheme: &str) -> Result<Phonet, RuleParseError>
+    {
+        let phonet = analyze_segment(grapheme).phonet;
+        if can_transcribe(phonet)
+        {
+            Ok(phonet)
+        }
+        else
+        {
+            Err(RuleParseError::UnknownSymbol(String::from(grapheme)))
+        }
+    }
+
+    fn parse_features(text: &str) -> Result<Vec<FeatureSpecification>, RuleParseError>
+    {
+        let mut specifications: Vec<FeatureSpecification> = Vec::new();
+        for word in text.split(|x: char| x.is_whitespace() || x == ',').filter(|word| !word.is_empty())
+        {
+            let (value, name) = match word.chars().next()
+            {
+                Some('+')             => (true, &word[1..]),
+                Some('-')             => (false, &word[1..]),
+                Some('−')             => (false, &word['−'.len_utf8()..]),
+                _ => return Err(RuleParseError::UnknownFeature(String::from(word))),
+            };
+            let feature = BinaryFeature::named(name).ok_or_else(|| RuleParseError::UnknownFeature(String::from(word)))?;
+            specifications.push(FeatureSpecification {feature, value});
+        }
+        Ok(specifications)
+    }
+
+    // Takes the text up to the closing bracket, leaving the
+    // characters after it.
+    fn take_until(characters: &mut std::iter::Peekable<std::str::Chars>, open: char, close: char) -> Result<String, RuleParseError>
+    {
+        let mut text = String::new();
+        let mut depth = 0;
+        for character in characters.by_ref()
+        {
+            if character == close && depth == 0
+            {
+                return Ok(text);
+            }
+            if character == open { depth += 1; }
+            if character == close { depth -= 1; }
+            text.push(character);
+        }
+        Err(RuleParseError::Unclosed(open))
+    }
+
+    /// Reads a sequence of environment items, such as "# (C) V*".
+    pub fn parse_environment(text: &str) -> Result<Vec<EnvironmentItem>, RuleParseError>
+    {
+        let mut items: Vec<EnvironmentItem> = Vec::new();
+        let mut characters = text.chars().peekable();
+        while let Some(character) = characters.next()
+        {
+            match character
+            {
+                '#' => items.push(EnvironmentItem::Boundary),
+                '(' => items.push(EnvironmentItem::Optional(parse_environment(&take_until(&mut characters, '(', ')')?)?)),
+                '[' => items.push(EnvironmentItem::Segment(Matcher::Features(parse_features(&take_until(&mut characters, '[', ']')?)?))),
+                '{' =>
+                {
+                    let members = take_until(&mut characters, '{', '}')?;
+                    let phonets = members.split(|x: char| x.is_whitespace() || x == ',')
+                                         .filter(|x| !x.is_empty())
+                                         .map(parse_symbol)
+                                         .collect::<Result<Vec<Phonet>, RuleParseError>>()?;
+                    items.push(EnvironmentItem::Segment(Matcher::Set(phonets)));
+                },
+                '*' =>
+                {
+                    let last = items.pop().ok_or(RuleParseError::NothingToRepeat)?;
+                    items.push(EnvironmentItem::Repeated(Box::new(last)));
+                },
+                x if x.is_whitespace() => (),
+                x if SPECIAL.contains(&x) => return Err(RuleParseError::Unexpected(x)),
+                x =>
+                {
+                    let mut symbols = x.to_string();
+                    while let Some(next) = characters.next_if(|y| !y.is_whitespace() && !SPECIAL.contains(y))
+                    {
+                        symbols.push(next);
+                    }
+                    for grapheme in split_graphemes(&symbols)
+                    {
+                        let matcher = match grapheme.as_str()
+                        {
+                            "C" => Matcher::Consonant,
+                            "V" => Matcher::Vowel,
+                            _   => Matcher::Phonet(parse_symbol(&grapheme)?),
+                        };
+                        items.push(EnvironmentItem::Segment(matcher));
+                    }
+                },
+            }
+        }
+        Ok(items)
+    }
+
+    fn parse_target(text: &str) -> Result<Target, RuleParseError>
+    {
+        if is_nothing(text)
+        {
+            return Ok(Target::Nothing);
+        }
+        let mut matchers: Vec<Matcher> = Vec::new();
+        for item in parse_environment(text)?
+        {
+            match item
+            {
+                EnvironmentItem::Segment(matcher) => matchers.push(matcher),
+                _ => return Err(RuleParseError::TargetNotASegment),
+            }
+        }
+        if matchers.is_empty() { Err(RuleParseError::EmptyTarget) } else { Ok(Target::Segments(matchers)) }
+    }
+
+    fn parse_change(text: &str) -> Result<Change, RuleParseError>
+    {
+        if is_nothing(text)
+        {
+            return Ok(Change::Deletion);
+        }
+        if let Some(inside) = text.strip_prefix('[')
+        {
+            let inside = inside.strip_suffix(']').ok_or(RuleParseError::Unclosed('['))?;
+            return Ok(Change::Features(parse_features(inside)?));
+        }
+        let phonets = split_graphemes(text).iter().map(|x| parse_symbol(x)).collect::<Result<Vec<Phonet>, RuleParseError>>()?;
+        if phonets.is_empty() { Err(RuleParseError::EmptyChange) } else { Ok(Change::Phonets(phonets)) }
+    }
+
+    /// Reads a rule such as "[+voice -son] -> [-voice] / _ #"
+    /// or "p t k → f θ x / V _ V". The environment is optional.
+    /// In it, # is the edge of the word, C and V are any consonant
+    /// and any vowel, (...) is optional, X* is any number of X,
+    /// {a, b} is any of a and b, and [...] is a feature bundle.
+    /// 0 (or ∅) as the target inserts, and as the change deletes.
+    pub fn parse_rule(text: &str) -> Result<Rule, RuleParseError>
+    {
+        let (target_text, rest) = text.split_once("->")
+                                      .or_else(|| text.split_once('→'))
+                                      .ok_or(RuleParseError::MissingArrow)?;
+        let (change_text, environment_text) = match rest.split_once('/')
+        {
+            Some((change, environment)) => (change, Some(environment)),
+            None                        => (rest, None),
+        };
+
+        let target = parse_target(target_text.trim())?;
+        let change = parse_change(change_text.trim())?;
+        let (left, right) = match environment_text
+        {
+            Some(environment) =>
+            {
+                let mut parts = environment.split('_');
+                let left = parts.next().unwrap_or("");
+                let right = parts.next().ok_or(RuleParseError::MissingFocus)?;
+                if parts.next().is_some()
+                {
+                    return Err(RuleParseError::SeveralFoci);
+                }
+                (parse_environment(left)?, parse_environment(right)?)
+            },
+            None => (Vec::new(), Vec::new()),
+        };
+
+        match (&target, &change)
+        {
+            (Target::Nothing, Change::Phonets(_)) => (),
+            (Target::Nothing, _) => return Err(RuleParseError::NothingToInsert),
+            (Target::Segments(targets), Change::Phonets(changes)) if changes.len() != 1 && changes.len() != targets.len() =>
+              return Err(RuleParseError::ChangeCountMismatch {targets: targets.len(), changes: changes.len()}),
+            _ => (),
+        }
+        Ok(Rule {target, change, left, right})
+    }
+
+
+    impl fmt::Display for Matcher
+    {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            match self
+            {
+                Matcher::Phonet(phonet) => write!(f, "{}", construct_transcription(*phonet)),
+                Matcher::Set(phonets) =>
+                  write!(f, "{{{}}}", phonets.iter().map(|x| construct_transcription(*x)).collect::<Vec<String>>().join(", ")),
+                Matcher::Features(specifications) =>
+                  write!(f, "[{}]", specifications.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(" ")),
+                Matcher::Consonant => write!(f, "C"),
+                Matcher::Vowel     => write!(f, "V"),
+            }
+        }
+    }
+
+    impl fmt::Display for EnvironmentItem
+    {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            match self
+            {
+                EnvironmentItem::Boundary         => write!(f, "#"),
+                EnvironmentItem::Segment(matcher) => write!(f, "{}", matcher),
+                EnvironmentItem::Optional(items)  => write!(f, "({})", join(items)),
+                EnvironmentItem::Repeated(item)   => write!(f, "{}*", item),
+            }
+        }
+    }
+
+    fn join<T: fmt::Display>(items: &[T]) -> String
+    {
+        items.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(" ")
+    }
+
+    impl fmt::Display for Rule
+    {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            let target = match &self.target
+            {
+                Target::Nothing            => String::from("∅"),
+                Target::Segments(matchers) => join(matchers),
+            };
+            let change = match &self.change
+            {
+                Change::Deletion          => String::from("∅"),
+                Change::Phonets(phonets)  => phonets.iter().map(|x| construct_transcription(*x)).collect::<Vec<String>>().join(" "),
+                Change::Features(specifications) => format!("[{}]", join(specifications)),
+            };
+            write!(f, "{} → {}", target, change)?;
+            if !self.left.is_empty() || !self.right.is_empty()
+            {
+                let left = join(&self.left);
+                let right = join(&self.right);
+                write!(f, " / {}_{}", if left.is_empty() { left } else { left + " " }, if right.is_empty() { right } else { String::from(" ") + &right })?;
+            }
+            Ok(())
+        }
+    }
+
+
+    // The boundaries between segments (0 to the length of the word)
+    // that a sequence of items can reach from a boundary, going
+    // forward (to the right) or backward (to the left).
+    fn reachable(items: &[EnvironmentItem], segments: &[Segment], start: usize, forward: bool) -> Vec<usize>
+    {
+        let mut positions = vec![start];
+        let ordered: Vec<&EnvironmentItem> = if forward { items.iter().collect() } else { items.iter().rev().collect() };
+        for item in ordered
+        {
+            let mut next: Vec<usize> = Vec::new();
+            for position in positions
+            {
+                for reached in reachable_by_item(item, segments, position, forward)
+                {
+                    if !next.contains(&reached)
+                    {
+                        next.push(reached);
+                    }
+                }
+            }
+            positions = next;
+        }
+        positions
+    }
+
+    fn reachable_by_item(item: &EnvironmentItem, segments: &[Segment], position: usize, forward: bool) -> Vec<usize>
+    {
+        match item
+        {
+            // Read forward, the right environment can only meet the end
+            // of the word, and read backward the left one only its start.
+            EnvironmentItem::Boundary =>
+              if position == if forward { segments.len() } else { 0 } { vec![position] } else { Vec::new() },
+            EnvironmentItem::Segment(matcher) =>
+            {
+                let index = if forward { Some(position) } else { position.checked_sub(1) };
+                match index.and_then(|i| segments.get(i))
+                {
+                    Some(segment) if matcher.matches(*segment) => vec![if forward { position + 1 } else { position - 1 }],
+                    _ => Vec::new(),
+                }
+            },
+            EnvironmentItem::Optional(items) =>
+            {
+                let mut positions = vec![position];
+                for reached in reachable(items, segments, position, forward)
+                {
+                    if !positions.contains(&reached)
+                    {
+                        positions.push(reached);
+                    }
+                }
+                positions
+            },
+            EnvironmentItem::Repeated(item) =>
+            {
+                let mut positions = vec![position];
+                let mut i = 0;
+                while i < positions.len()
+                {
+                    for reached in reachable_by_item(item, segments, positions[i], forward)
+                    {
+                        if !positions.contains(&reached)
+                        {
+                            positions.push(reached);
+                        }
+                    }
+                    i += 1;
+                }
+                positions
+            },
+        }
+    }
+
+    fn environment_holds(rule: &Rule, segments: &[Segment], left_end: usize, right_start: usize) -> bool
+    {
+        !reachable(&rule.left, segments, left_end, false).is_empty()
+          && !reachable(&rule.right, segments, right_start, true).is_empty()
+    }
+
+    /// Where a rule applies in a word. For an insertion these are
+    /// boundaries between segments (0 is before the first one),
+    /// for other rules they are the indexes of the target segments.
+    pub fn application_sites(rule: &Rule, segments: &[Segment]) -> Vec<usize>
+    {
+        match &rule.target
+        {
+            Target::Nothing =>
+              (0..=segments.len()).filter(|i| environment_holds(rule, segments, *i, *i)).collect(),
+            Target::Segments(matchers) =>
+              (0..segments.len()).filter(|i| matchers.iter().any(|x| x.matches(segments[*i]))
+                                             && environment_holds(rule, segments, *i, i + 1)).collect(),
+        }
+    }
+
+    /// What a rule turns one of its target segments into,
+    /// or None if it deletes it.
+    pub fn rewritten_segment(rule: &Rule, segment: Segment) -> Option<Segment>
+    {
+        let alternative = match &rule.target
+        {
+            Target::Segments(matchers) => matchers.iter().position(|x| x.matches(segment)).unwrap_or(0),
+            Target::Nothing => 0,
+        };
+        match &rule.change
+        {
+            Change::Deletion => None,
+            Change::Phonets(phonets) =>
+            {
+                let phonet = if phonets.len() == 1 { phonets[0] } else { phonets[alternative] };
+                Some(Segment {phonet, ..segment})
+            },
+            // A segment that cannot take the features stays as it is.
+            Change::Features(specifications) =>
+              Some(Segment {phonet: with_binary_features(segment.phonet, specifications).unwrap_or(segment.phonet), ..segment}),
+        }
+    }
+
+    /// Applies a rule to a word, at every site at once: the sites
+    /// are all found in the input, so the rule does not feed itself.
+    pub fn apply_rule(rule: &Rule, segments: &[Segment]) -> Vec<Segment>
+    {
+        let sites = application_sites(rule, segments);
+        let mut result: Vec<Segment> = Vec::new();
+        match (&rule.target, &rule.change)
+        {
+            (Target::Nothing, Change::Phonets(phonets)) =>
+            {
+                for i in 0..=segments.len()
+                {
+                    if sites.contains(&i)
+                    {
+                        result.extend(phonets.iter().map(|x| Segment::new(*x)));
+                    }
+                    if let Some(segment) = segments.get(i)
+                    {
+                        result.push(*segment);
+                    }
+                }
+            },
+            _ =>
+            {
+                for (i, segment) in segments.iter().enumerate()
+                {
+                    if !sites.contains(&i)
+                    {
+                        result.push(*segment);
+                    }
+                    else if let Some(changed) = rewritten_segment(rule, *segment)
+                    {
+                        result.push(changed);
+                    }
+                }
+            },
+        }
+        result
+    }
+
+    /// Applies a rule to each word of a transcription
+    /// (words are separated by spaces).
+    pub fn apply_rule_to_transcription(rule: &Rule, text: &str) -> String
+    {
+        text.split_whitespace()
+            .map(|word| construct_segments_transcription(&apply_rule(rule, &parse_transcription(word))))
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+
+    #[cfg(test)]
+    mod rewrite_tests
+    {
+        use super::*;
+
+        fn apply(rule: &str, text: &str) -> String
+        {
+            apply_rule_to_transcription(&parse_rule(rule).unwrap(), text)
+        }
+
+        #[test]
+        fn parse_rule_test_final_devoicing()
+        {
+            let rule = parse_rule("[+voice -son] -> [-voice] / _ #").unwrap();
+            assert_eq!(rule.to_string(), "[+voice -son] → [-voice] / _ #");
+            assert_eq!(apply_rule_to_transcription(&rule, "hund bad ban"), "hunt bat ban");
+        }
+
+        #[test]
+        fn apply_rule_test_parallel_spirantization()
+        {
+            assert_eq!(apply("p t k -> f θ x / V _ V", "apa ata aka akt"), "afa aθa axa akt");
+        }
+
+        #[test]
+        fn apply_rule_test_optional_star_and_insertion()
+        {
+            assert_eq!(apply("V -> [+round] / _ C* u", "pitku pitka"), "pytku pitka");
+            assert_eq!(apply("0 -> ə / # s _ C", "skola"), "səkola");
+            assert_eq!(apply("{e, o} -> 0 / _ (C) #", "kates katem"), "kats katm");
+            assert_eq!(parse_rule("p -> f / V _ _"), Err(RuleParseError::SeveralFoci));
+            assert_eq!(parse_rule("p t -> f θ x"), Err(RuleParseError::ChangeCountMismatch {targets: 2, changes: 3}));
+        }
+
+        #[test]
+        fn apply_rule_test_insertion_at_one_edge()
+        {
+            assert_eq!(apply("0 -> ə / _ #", "at"), "atə");
+            assert_eq!(apply("0 -> ə / # _", "skola"), "əskola");
+            assert_eq!(apply("a -> e / # _", "aba"), "eba");
+        }
+    }
+}
@@ -0,0 +1,347 @@
+pub mod sound_change
+{
+    use std::fmt;
+    use crate::lib::{Phonet, Phonet::*, VocalFolds, VocalFolds::*, Place::*, Manner::*, voiced_phonet, devoiced_phonet, spirantized_phonet, deaspirated_phonet};
+    use crate::segment::segment::{Segment, Stress::*, syllable_stress};
+    use crate::grapheme::international_phonetic_alphabet::international_phonetic_alphabet::construct_transcription;
+    use crate::grapheme::transcription::transcription::construct_segments_transcription;
+    use crate::rule::rewrite::rewrite::{Rule, apply_rule, parse_rule};
+
+    /// What a sound change does.
+    #[derive(Debug, Clone)]
+    pub enum ChangeKind
+    {
+        // A rewrite rule, such as "p t k -> b d g / V _ V".
+        Rule(Rule),
+
+        // Every segment that is a target changes its phonet,
+        // where the condition holds for the word and its position.
+        Phonets
+        {
+            target: fn(Segment) -> bool,
+            change: fn(Phonet) -> Phonet,
+            condition: fn(&[Segment], usize) -> bool
+        }
+    }
+
+    /// A sound change, with the date it happened
+    /// (a year, negative before the common era).
+    #[derive(Debug, Clone)]
+    pub struct SoundChange
+    {
+        pub name: String,
+        pub date: i32,
+        pub kind: ChangeKind
+    }
+
+    impl SoundChange
+    {
+        /// A sound change stated as a rewrite rule.
+        /// Gives an error naming the rule if it cannot be read.
+        pub fn rule(name: &str, date: i32, rule: &str) -> Result<SoundChange, String>
+        {
+            let parsed = parse_rule(rule).map_err(|error| format!("{}: {}", rule, error))?;
+            Ok(SoundChange {name: String::from(name), date, kind: ChangeKind::Rule(parsed)})
+        }
+
+        /// Applies the change to one word.
+        pub fn apply(&self, segments: &[Segment]) -> Vec<Segment>
+        {
+            match &self.kind
+            {
+                ChangeKind::Rule(rule) => apply_rule(rule, segments),
+                ChangeKind::Phonets {target, change, condition} =>
+                  segments.iter().enumerate().map(|(i, segment)|
+                    if target(*segment) && condition(segments, i) { Segment {phonet: change(segment.phonet), ..*segment} } else { *segment }
+                  ).collect(),
+            }
+        }
+    }
+
+
+    fn is_stop(segment: Segment, voicing: VocalFolds) -> bool
+    {
+        matches!(segment.phonet, Consonant {manner: Plosive, vocal_folds, ..} if vocal_folds == voicing)
+    }
+
+    fn is_voiceless_fricative(segment: Segment) -> bool
+    {
+        matches!(segment.phonet, Consonant {manner: Fricative, vocal_folds: Voiceless, ..})
+    }
+
+    fn anywhere(_: &[Segment], _: usize) -> bool
+    {
+        true
+    }
+
+    fn not_after_s(segments: &[Segment], index: usize) -> bool
+    {
+        index == 0 || !matches!(segments[index - 1].phonet, Consonant {place: Alveolar, manner: Fricative, ..})
+    }
+
+    // Not at the start of the word, after a voiced segment,
+    // and the syllable before is not stressed.
+    fn after_unstressed_syllable(segments: &[Segment], index: usize) -> bool
+    {
+        let after_voiced = index > 0 && !matches!(segments[index - 1].phonet, Consonant {vocal_folds: Voiceless, ..});
+        let vowel_before = (0..index).rev().find(|i| matches!(segments[*i].phonet, Vowel {..}));
+        after_voiced && vowel_before.map(|i| syllable_stress(segments, i) == Unstressed).unwrap_or(false)
+    }
+
+    /// A list of sound changes, in the order they happened.
+    #[derive(Debug, Clone)]
+    pub struct SoundChangeCascade
+    {
+        pub name: String,
+        pub changes: Vec<SoundChange>
+    }
+
+    impl SoundChangeCascade
+    {
+        /// Grimm's law, from Proto-Indo-European to Proto-Germanic:
+        /// voiceless stops become fricatives (not after s), voiced stops
+        /// become voiceless, and breathy voiced stops become plain voiced.
+        pub fn grimms_law() -> SoundChangeCascade
+        {
+            let step = |name: &str, date: i32, target: fn(Segment) -> bool, change: fn(Phonet) -> Phonet, condition: fn(&[Segment], usize) -> bool|
+              SoundChange {name: String::from(name), date, kind: ChangeKind::Phonets {target, change, condition}};
+            SoundChangeCascade
+            { name: String::from("Grimm's law")
+            , changes: vec![ step("voiceless stops become fricatives", -500, |x| is_stop(x, Voiceless), spirantized_phonet, not_after_s)
+                           , step("voiced stops become voiceless", -450, |x| is_stop(x, Voiced), devoiced_phonet, anywhere)
+                           , step("breathy voiced stops become voiced", -400, |x| is_stop(x, VoicedAspirated), deaspirated_phonet, anywhere)
+                           ]
+            }
+        }
+
+        /// Verner's law: voiceless fricatives become voiced when the
+        /// syllable before them is not stressed (PIE *pəˈtēr, Gothic fadar).
+        pub fn verners_law() -> SoundChangeCascade
+        {
+            SoundChangeCascade
+            { name: String::from("Verner's law")
+            , changes: vec![SoundChange { name: String::from("voiceless fricatives become voiced after an unstressed syllable")
+                                        , date: -300
+                                        , kind: ChangeKind::Phonets {target: is_voiceless_fricative, change: voiced_phonet, condition: after_unstressed_syllable}
+                                        }]
+            }
+        }
+
+        /// Grimm's law and then Verner's law.
+        pub fn proto_germanic() -> SoundChangeCascade
+        {
+            SoundChangeCascade {name: String::from("Proto-Germanic"), ..Self::grimms_law()}.then(Self::verners_law())
+        }
+
+        /// Western Romance lenition from Latin to Spanish: stops between
+        /// vowels are voiced (lupu → lobo), and later voiced stops
+        /// between vowels become fricatives (vida → [biða]).
+        pub fn latin_to_spanish_lenition() -> SoundChangeCascade
+        {
+            SoundChangeCascade
+            { name: String::from("Latin to Spanish lenition")
+            , changes: vec![ SoundChange::rule("voicing between vowels", 500, "[-voice -son -cont] -> [+voice] / V _ V").expect("preset rule")
+                           , SoundChange::rule("spirantization between vowels", 1500, "b d g -> β ð ɣ / V _ V").expect("preset rule")
+                           ]
+            }
+        }
+
+        /// This cascade followed by another one.
+        pub fn then(mut self, other: SoundChangeCascade) -> SoundChangeCascade
+        {
+            self.changes.extend(other.changes);
+            self
+        }
+    }
+
+
+    /// One step of the history of a word.
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub struct HistoryStep
+    {
+        pub date: i32,
+        pub change: String,
+        pub form: Vec<Segment>
+    }
+
+    /// The history of one word: its proto-form and the form after each change.
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub struct WordHistory
+    {
+        pub proto: Vec<Segment>,
+        pub steps: Vec<HistoryStep>
+    }
+
+    impl WordHistory
+    {
+        /// The form after the last change.
+        pub fn descendant(&self) -> &[Segment]
+        {
+            self.steps.last().map(|x| x.form.as_slice()).unwrap_or(&self.proto)
+        }
+    }
+
+    /// The proto-form, then each change that did something to the word.
+    impl fmt::Display for WordHistory
+    {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            let mut lines: Vec<String> = vec![format!("*{}", construct_segments_transcription(&self.proto))];
+            let mut previous = &self.proto;
+            for step in self.steps.iter()
+            {
+                if step.form != *previous
+                {
+                    lines.push(format!("{}, {}: {}", step.date, step.change, construct_segments_transcription(&step.form)));
+                }
+                previous = &step.form;
+            }
+            write!(f, "{}", lines.join("\n"))
+        }
+    }
+
+    /// A change to the sound system found in the lexicon.
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub enum InventoryEvent
+    {
+        // Sounds that were distinct became the same sound, at least in some words.
+        Merger {date: i32, change: String, sources: Vec<Phonet>, result: Phonet},
+
+        // One sound became two, depending on where it was.
+        Split {date: i32, change: String, source: Phonet, results: Vec<Phonet>}
+    }
+
+    fn ipa_list(phonets: &[Phonet]) -> String
+    {
+        phonets.iter().map(|x| construct_transcription(*x)).collect::<Vec<String>>().join(", ")
+    }
+
+    impl fmt::Display for InventoryEvent
+    {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            match self
+            {
+                InventoryEvent::Merger {date, change, sources, result} =>
+                  write!(f, "{}, {}: merger of {} into {}", date, change, ipa_list(sources), construct_transcription(*result)),
+                InventoryEvent::Split {date, change, source, results} =>
+                  write!(f, "{}, {}: split of {} into {}", date, change, construct_transcription(*source), ipa_list(results)),
+            }
+        }
+    }
+
+    /// The result of running a cascade over a lexicon.
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub struct SimulationResult
+    {
+        pub histories: Vec<WordHistory>,
+        pub events: Vec<InventoryEvent>
+    }
+
+    // The mergers and splits of one change, found by pairing the segments
+    // of each word before and after it. Words whose length changed
+    // cannot be paired, and are left out.
+    fn inventory_events(change: &SoundChange, before: &[Vec<Segment>], after: &[Vec<Segment>]) -> Vec<InventoryEvent>
+    {
+        let mut pairs: Vec<(Phonet, Phonet)> = Vec::new();
+        for (old, new) in before.iter().zip(after.iter()).filter(|(old, new)| old.len() == new.len())
+        {
+            for pair in old.iter().zip(new.iter()).map(|(x, y)| (x.phonet, y.phonet))
+            {
+                if !pairs.contains(&pair)
+                {
+                    pairs.push(pair);
+                }
+            }
+        }
+
+        let mut events: Vec<InventoryEvent> = Vec::new();
+        let mut seen: Vec<Phonet> = Vec::new();
+        for (_, result) in pairs.iter()
+        {
+            let sources: Vec<Phonet> = pairs.iter().filter(|(_, y)| y == result).map(|(x, _)| *x).collect();
+            if sources.len() > 1 && !seen.contains(result) && sources.iter().any(|x| x != result)
+            {
+                events.push(InventoryEvent::Merger {date: change.date, change: change.name.clone(), sources, result: *result});
+            }
+            seen.push(*result);
+        }
+        let mut seen: Vec<Phonet> = Vec::new();
+        for (source, _) in pairs.iter()
+        {
+            let results: Vec<Phonet> = pairs.iter().filter(|(x, _)| x == source).map(|(_, y)| *y).collect();
+            if results.len() > 1 && !seen.contains(source)
+            {
+                events.push(InventoryEvent::Split {date: change.date, change: change.name.clone(), source: *source, results});
+            }
+            seen.push(*source);
+        }
+        events
+    }
+
+    /// Runs a cascade of sound changes over the words of a proto-language,
+    /// keeping the history of every word and the mergers and splits
+    /// that each change made.
+    pub fn simulate(cascade: &SoundChangeCascade, lexicon: &[Vec<Segment>]) -> SimulationResult
+    {
+        let mut histories: Vec<WordHistory> = lexicon.iter().map(|word| WordHistory {proto: word.clone(), steps: Vec::new()}).collect();
+        let mut current: Vec<Vec<Segment>> = lexicon.to_vec();
+        let mut events: Vec<InventoryEvent> = Vec::new();
+        for change in cascade.changes.iter()
+        {
+            let next: Vec<Vec<Segment>> = current.iter().map(|word| change.apply(word)).collect();
+            events.extend(inventory_events(change, &current, &next));
+            for (history, form) in histories.iter_mut().zip(next.iter())
+            {
+                history.steps.push(HistoryStep {date: change.date, change: change.name.clone(), form: form.clone()});
+            }
+            current = next;
+        }
+        SimulationResult {histories, events}
+    }
+
+
+    #[cfg(test)]
+    mod sound_change_tests
+    {
+        use super::*;
+        use crate::grapheme::transcription::transcription::parse_transcription;
+
+        fn lexicon(words: &[&str]) -> Vec<Vec<Segment>>
+        {
+            words.iter().map(|x| parse_transcription(x)).collect()
+        }
+
+        fn descendants(result: &SimulationResult) -> Vec<String>
+        {
+            result.histories.iter().map(|x| construct_segments_transcription(x.descendant())).collect()
+        }
+
+        #[test]
+        fn simulate_test_grimm_and_verner()
+        {
+            let result = simulate(&SoundChangeCascade::proto_germanic(), &lexicon(&["paˈter", "ˈbʰrater", "ˈdekem"]));
+            assert_eq!(descendants(&result), ["ɸaˈðer", "ˈbraθer", "ˈtexem"]);
+            assert_eq!(result.histories[0].to_string(), "*paˈter\n\
+                                                         -500, voiceless stops become fricatives: ɸaˈθer\n\
+                                                         -300, voiceless fricatives become voiced after an unstressed syllable: ɸaˈðer");
+            let split = result.events.iter().find(|x| matches!(x, InventoryEvent::Split {..})).unwrap();
+            assert_eq!(split.to_string(), "-300, voiceless fricatives become voiced after an unstressed syllable: split of θ into ð, θ");
+        }
+
+        #[test]
+        fn simulate_test_latin_to_spanish()
+        {
+            let result = simulate(&SoundChangeCascade::latin_to_spanish_lenition(), &lexicon(&["lupu", "wita", "kaballu"]));
+            assert_eq!(descendants(&result), ["luβu", "wiða", "kaβallu"]);
+            assert_eq!(result.events[0].to_string(), "500, voicing between vowels: merger of p, b into b");
+        }
+
+        #[test]
+        fn sound_change_rule_test_unreadable()
+        {
+            let error = SoundChange::rule("nothing", 0, "p t k / V _ V").unwrap_err();
+            assert_eq!(error, "p t k / V _ V: the rule has no -> (or →)");
+        }
+    }
+}
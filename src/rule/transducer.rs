@@ -0,0 +1,882 @@
+pub mod transducer
+{
+    use std::fmt;
+    use std::cell::RefCell;
+    use std::collections::{HashMap, HashSet};
+    use crate::lib::Phonet;
+    use crate::segment::segment::Segment;
+    use crate::grapheme::international_phonetic_alphabet::international_phonetic_alphabet::construct_transcription;
+    use crate::rule::rewrite::rewrite::{Rule, Matcher, Target, Change, EnvironmentItem, application_sites, rewritten_segment, apply_rule};
+
+    /// The most states a transducer may have. Compiling or composing
+    /// stops with an error when a machine would be bigger.
+    pub static MAX_STATES: usize = 100_000;
+
+    /// A deterministic finite-state transducer over phonets, which
+    /// reads a word one phonet at a time and writes the rewritten word.
+    /// It starts in state 0. Since a rule can look ahead, some output
+    /// is held back and written at the end, from `final_output`.
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub struct Transducer
+    {
+        // The phonets it can read.
+        pub alphabet: Vec<Phonet>,
+
+        // For each state and each phonet of the alphabet (in its order),
+        // the next state and what is written.
+        pub transitions: Vec<Vec<(usize, Vec<Phonet>)>>,
+
+        // What is written when the word ends in each state.
+        pub final_output: Vec<Vec<Phonet>>,
+
+        // The position of each phonet in the alphabet.
+        symbols: HashMap<Phonet, usize>
+    }
+
+    /// Why a rule or cascade could not be compiled.
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub enum CompileError
+    {
+        // A Kleene star makes the environment unbounded, which
+        // needs more than a fixed window of the word.
+        UnboundedEnvironment,
+
+        // An insertion of more than one segment.
+        MultipleInsertion,
+
+        // The machine would have more than MAX_STATES states.
+        TooManyStates,
+
+        // A machine wrote a phonet that the next one cannot read.
+        UnknownSymbol(Phonet)
+    }
+
+    impl fmt::Display for CompileError
+    {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            match self
+            {
+                CompileError::UnboundedEnvironment => write!(f, "environments with * cannot be compiled"),
+                CompileError::MultipleInsertion    => write!(f, "insertions of more than one segment cannot be compiled"),
+                CompileError::TooManyStates        => write!(f, "the transducer would have more than {} states", MAX_STATES),
+                CompileError::UnknownSymbol(x)     => write!(f, "{} is not in the alphabet", construct_transcription(*x)),
+            }
+        }
+    }
+
+
+    // The most segments a sequence of environment items can span,
+    // or None if there is no limit.
+    fn span(items: &[EnvironmentItem]) -> Option<usize>
+    {
+        items.iter().map(|item| match item
+        {
+            EnvironmentItem::Boundary     => Some(0),
+            EnvironmentItem::Segment(_)   => Some(1),
+            EnvironmentItem::Optional(xs) => span(xs),
+            EnvironmentItem::Repeated(_)  => None,
+        }).sum()
+    }
+
+    fn has_boundary(items: &[EnvironmentItem]) -> bool
+    {
+        items.iter().any(|item| match item
+        {
+            EnvironmentItem::Boundary     => true,
+            EnvironmentItem::Segment(_)   => false,
+            EnvironmentItem::Optional(xs) => has_boundary(xs),
+            EnvironmentItem::Repeated(x)  => has_boundary(std::slice::from_ref(x.as_ref())),
+        })
+    }
+
+    fn environment_matchers<'a>(items: &'a [EnvironmentItem], matchers: &mut Vec<&'a Matcher>)
+    {
+        for item in items.iter()
+        {
+            match item
+            {
+                EnvironmentItem::Segment(matcher) => matchers.push(matcher),
+                EnvironmentItem::Optional(xs)     => environment_matchers(xs, matchers),
+                EnvironmentItem::Repeated(x)      => environment_matchers(std::slice::from_ref(x.as_ref()), matchers),
+                EnvironmentItem::Boundary         => (),
+            }
+        }
+    }
+
+    // What every state of a rule's machine holds: a window of the input,
+    // with None past the edges of the word, and the phonets of the window
+    // that are not written yet (the last ones of the word), in order.
+    // The window keeps, for each phonet, the first one of the alphabet that
+    // no matcher of the rule tells apart from it, so that phonets that behave
+    // the same share a state; only the phonets still to be written are kept
+    // as they are.
+    #[derive(Eq, PartialEq, Hash, Debug, Clone)]
+    struct WindowState
+    {
+        cells: Vec<Option<Phonet>>,
+        held: Vec<Phonet>
+    }
+
+    struct Window<'a>
+    {
+        rule: &'a Rule,
+        left: usize,
+
+        // The cells after the middle one: what the right environment
+        // can reach, and one more if it has a #.
+        right: usize,
+
+        // What the first cell of the window holds when it is in the word
+        // (there is nothing to read if the alphabet is empty).
+        edge: Option<Phonet>,
+
+        // The representative of each phonet, and whether a target matches it.
+        representatives: HashMap<Phonet, (Phonet, bool)>,
+
+        // Whether the rule applies at the middle of each window seen so far.
+        applies: RefCell<HashMap<Vec<Option<Phonet>>, bool>>
+    }
+
+    impl<'a> Window<'a>
+    {
+        // The window of a rule that reads words made of the alphabet.
+        fn new(rule: &'a Rule, alphabet: &[Phonet]) -> Result<Window<'a>, CompileError>
+        {
+            let left = span(&rule.left).ok_or(CompileError::UnboundedEnvironment)?;
+            let right = span(&rule.right).ok_or(CompileError::UnboundedEnvironment)?;
+            if let (Target::Nothing, Change::Phonets(inserted)) = (&rule.target, &rule.change)
+            {
+                if inserted.len() > 1
+                {
+                    return Err(CompileError::MultipleInsertion);
+                }
+            }
+
+            let mut targets: Vec<&Matcher> = Vec::new();
+            if let Target::Segments(matchers) = &rule.target
+            {
+                targets.extend(matchers.iter());
+            }
+            let mut matchers: Vec<&Matcher> = targets.clone();
+            environment_matchers(&rule.left, &mut matchers);
+            environment_matchers(&rule.right, &mut matchers);
+
+            // The phonets that every matcher of the rule matches or not alike
+            // behave the same, and share one representative.
+            let mut representatives: HashMap<Phonet, (Phonet, bool)> = HashMap::new();
+            let mut classes: HashMap<Vec<bool>, Phonet> = HashMap::new();
+            for phonet in alphabet.iter()
+            {
+                let signature: Vec<bool> = matchers.iter().map(|x| x.matches(Segment::new(*phonet))).collect();
+                let is_target = signature[..targets.len()].contains(&true);
+                representatives.insert(*phonet, (*classes.entry(signature).or_insert(*phonet), is_target));
+            }
+
+            // One cell more on the left than the environment can reach, so that
+            // the edge of the window is never taken for the edge of the word.
+            // On the right that is only needed to find a #, and every cell
+            // more there holds back what is written.
+            let margin = if has_boundary(&rule.right) { 1 } else { 0 };
+            Ok(Window {rule, left, right: right + margin, edge: alphabet.first().copied(), representatives, applies: RefCell::new(HashMap::new())})
+        }
+
+        fn initial(&self) -> WindowState
+        {
+            WindowState {cells: vec![None; self.left + 1 + self.right], held: Vec::new()}
+        }
+
+        fn inserted(&self) -> Option<&'a [Phonet]>
+        {
+            match (&self.rule.target, &self.rule.change)
+            {
+                (Target::Nothing, Change::Phonets(inserted)) => Some(inserted),
+                _ => None,
+            }
+        }
+
+        fn is_target(&self, phonet: Phonet) -> bool
+        {
+            self.representatives[&phonet].1
+        }
+
+        fn representative(&self, phonet: Phonet) -> Phonet
+        {
+            self.representatives[&phonet].0
+        }
+
+        // A phonet that no target matches stays as it is, so it is written
+        // as soon as the ones before it are (unless something may still be
+        // inserted before it). The last `keep` held phonets are not written.
+        fn flush(&self, held: &mut Vec<Phonet>, keep: usize, output: &mut Vec<Phonet>)
+        {
+            if self.inserted().is_none()
+            {
+                while held.len() > keep && !self.is_target(held[0])
+                {
+                    output.push(held.remove(0));
+                }
+            }
+        }
+
+        fn applies_at_middle(&self, window: &[Option<Phonet>]) -> bool
+        {
+            if let Some(applies) = self.applies.borrow().get(window)
+            {
+                return *applies;
+            }
+            // The word around the middle: the cells up to the padding on either side.
+            let middle = self.left + 1;
+            let start = (0..middle).rev().find(|i| window[*i].is_none()).map_or(0, |i| i + 1);
+            let end = (middle..window.len()).find(|i| window[*i].is_none()).unwrap_or(window.len());
+            let segments: Vec<Segment> = window[start..end].iter().map(|x| Segment::new(x.unwrap())).collect();
+            let applies = application_sites(self.rule, &segments).contains(&(middle - start));
+            self.applies.borrow_mut().insert(window.to_vec(), applies);
+            applies
+        }
+
+        // Reads one more cell. Decides what the middle cell of the window
+        // becomes, along with any insertion before it, and writes it along
+        // with the held phonets after it that the rule cannot change.
+        fn step(&self, state: &WindowState, next: Option<Phonet>) -> (WindowState, Vec<Phonet>)
+        {
+            self.advance(state, next, 0)
+        }
+
+        fn advance(&self, state: &WindowState, next: Option<Phonet>, keep: usize) -> (WindowState, Vec<Phonet>)
+        {
+            let mut window: Vec<Option<Phonet>> = state.cells.clone();
+            window.push(next.map(|x| self.representative(x)));
+            let mut held: Vec<Phonet> = state.held.clone();
+            held.extend(next);
+            let middle = self.left + 1;
+
+            let applies = self.applies_at_middle(&window);
+
+            // Everything before the middle is written, so it is
+            // the first held phonet if it has not been written yet.
+            let first_held = window.iter().rposition(|x| x.is_some()).map(|last| last + 1 - held.len());
+            let unwritten = !held.is_empty() && first_held == Some(middle);
+
+            let mut output: Vec<Phonet> = Vec::new();
+            if let Some(inserted) = self.inserted()
+            {
+                let at_word = window[middle].is_some() || window[middle - 1].is_some();
+                if at_word && applies
+                {
+                    output.extend(inserted.iter().copied());
+                }
+            }
+            if unwritten
+            {
+                let phonet = held.remove(0);
+                match self.inserted()
+                {
+                    None if applies => output.extend(rewritten_segment(self.rule, Segment::new(phonet)).map(|x| x.phonet)),
+                    _               => output.push(phonet),
+                }
+            }
+            self.flush(&mut held, keep, &mut output);
+            window.remove(0);
+            // The environment cannot reach the first cell, which only tells
+            // whether the edge of the word is further back, so any phonet
+            // there is the same.
+            if window[0].is_some()
+            {
+                window[0] = self.edge;
+            }
+            (WindowState {cells: window, held}, output)
+        }
+
+        // What is written when the word ends.
+        fn finish(&self, state: &WindowState) -> Vec<Phonet>
+        {
+            // The empty word, which the window cannot
+            // tell from the space around a word.
+            if *state == self.initial()
+            {
+                return apply_rule(self.rule, &[]).iter().map(|x| x.phonet).collect();
+            }
+            let mut current = state.clone();
+            let mut output: Vec<Phonet> = Vec::new();
+            for _ in 0..=self.right
+            {
+                let (next, written) = self.step(&current, None);
+                output.extend(written);
+                current = next;
+            }
+            output
+        }
+    }
+
+    // Builds a machine by exploring every state reachable over the
+    // alphabet. `step` gives the next state and output for a state
+    // and the position of a phonet in the alphabet, `finish` the output
+    // when the word ends in a state. States are told apart by their key.
+    fn explore<K: Clone + Eq + std::hash::Hash>(alphabet: &[Phonet],
+                                                initial: K,
+                                                step: impl Fn(&K, usize) -> Result<(K, Vec<Phonet>), CompileError>,
+                                                finish: impl Fn(&K) -> Result<Vec<Phonet>, CompileError>) -> Result<Transducer, CompileError>
+    {
+        let mut keys: Vec<K> = vec![initial.clone()];
+        let mut index: HashMap<K, usize> = HashMap::new();
+        index.insert(initial, 0);
+        let mut transitions: Vec<Vec<(usize, Vec<Phonet>)>> = Vec::new();
+        let mut final_output: Vec<Vec<Phonet>> = Vec::new();
+        let mut state = 0;
+        while state < keys.len()
+        {
+            let key = keys[state].clone();
+            let mut row: Vec<(usize, Vec<Phonet>)> = Vec::new();
+            for symbol in 0..alphabet.len()
+            {
+                let (next, output) = step(&key, symbol)?;
+                let target = match index.get(&next)
+                {
+                    Some(target) => *target,
+                    None =>
+                    {
+                        if keys.len() == MAX_STATES
+                        {
+                            return Err(CompileError::TooManyStates);
+                        }
+                        index.insert(next.clone(), keys.len());
+                        keys.push(next);
+                        keys.len() - 1
+                    },
+                };
+                row.push((target, output));
+            }
+            transitions.push(row);
+            final_output.push(finish(&key)?);
+            state += 1;
+        }
+        let symbols: HashMap<Phonet, usize> = alphabet.iter().enumerate().map(|(i, x)| (*x, i)).collect();
+        Ok(Transducer {alphabet: alphabet.to_vec(), transitions, final_output, symbols})
+    }
+
+    /// Compiles a rewrite rule into a transducer that reads words made of
+    /// the alphabet, and applies the rule to them as apply_rule does.
+    /// The environments must be bounded (no *).
+    pub fn compile_rule(rule: &Rule, alphabet: &[Phonet]) -> Result<Transducer, CompileError>
+    {
+        let window = Window::new(rule, alphabet)?;
+        explore(alphabet,
+                window.initial(),
+                |state, symbol| Ok(window.step(state, Some(alphabet[symbol]))),
+                |state| Ok(window.finish(state)))
+    }
+
+    impl Transducer
+    {
+        fn symbol(&self, phonet: Phonet) -> Option<usize>
+        {
+            self.symbols.get(&phonet).copied()
+        }
+
+        // Reads a word from a state: the state it ends in and what it wrote,
+        // without the final output.
+        fn run(&self, state: usize, input: &[Phonet]) -> Result<(usize, Vec<Phonet>), CompileError>
+        {
+            let mut state = state;
+            let mut output: Vec<Phonet> = Vec::new();
+            for phonet in input.iter()
+            {
+                let symbol = self.symbol(*phonet).ok_or(CompileError::UnknownSymbol(*phonet))?;
+                let (next, written) = &self.transitions[state][symbol];
+                output.extend(written.iter().copied());
+                state = *next;
+            }
+            Ok((state, output))
+        }
+
+        /// Rewrites a word. Returns None if it has a phonet
+        /// that is not in the alphabet.
+        pub fn apply(&self, input: &[Phonet]) -> Option<Vec<Phonet>>
+        {
+            let (state, mut output) = self.run(0, input).ok()?;
+            output.extend(self.final_output[state].iter().copied());
+            Some(output)
+        }
+
+        /// Rewrites every word of a lexicon.
+        pub fn apply_to_lexicon(&self, lexicon: &[Vec<Phonet>]) -> Vec<Option<Vec<Phonet>>>
+        {
+            lexicon.iter().map(|word| self.apply(word)).collect()
+        }
+
+        pub fn state_count(&self) -> usize
+        {
+            self.transitions.len()
+        }
+
+        /// Every phonet the transducer can write.
+        pub fn output_alphabet(&self) -> Vec<Phonet>
+        {
+            let mut phonets: Vec<Phonet> = Vec::new();
+            let written = self.transitions.iter().flatten().map(|(_, output)| output).chain(self.final_output.iter());
+            for phonet in written.flatten()
+            {
+                if !phonets.contains(phonet)
+                {
+                    phonets.push(*phonet);
+                }
+            }
+            phonets
+        }
+
+        /// One transducer that does what this one and then `next` do,
+        /// minimized. `next` must be able to read everything this one writes.
+        pub fn compose(&self, next: &Transducer) -> Result<Transducer, CompileError>
+        {
+            let composed = explore(&self.alphabet,
+                                   (0, 0),
+                                   |(first, second), symbol|
+                                   {
+                                       let (after_first, written) = &self.transitions[*first][symbol];
+                                       let (after_second, output) = next.run(*second, written)?;
+                                       Ok(((*after_first, after_second), output))
+                                   },
+                                   |(first, second)|
+                                   {
+                                       let (after_second, mut output) = next.run(*second, &self.final_output[*first])?;
+                                       output.extend(next.final_output[after_second].iter().copied());
+                                       Ok(output)
+                                   })?;
+            Ok(composed.minimized())
+        }
+
+        /// The same transducer with as few states as merging gives:
+        /// states that write the same when the word ends, and that
+        /// write the same and go to merged states on every phonet,
+        /// become one.
+        pub fn minimized(&self) -> Transducer
+        {
+            // Split the states by what they write, when the word ends and on
+            // every phonet, then keep splitting them by the groups their
+            // transitions go to, until no group splits.
+            let mut outputs: HashMap<(&[Phonet], Vec<&[Phonet]>), usize> = HashMap::new();
+            let mut group: Vec<usize> = Vec::new();
+            for (row, output) in self.transitions.iter().zip(self.final_output.iter())
+            {
+                let count = outputs.len();
+                group.push(*outputs.entry((output, row.iter().map(|(_, written)| written.as_slice()).collect())).or_insert(count));
+            }
+            let mut count = outputs.len();
+            loop
+            {
+                let mut signatures: HashMap<(usize, Vec<usize>), usize> = HashMap::new();
+                let mut next: Vec<usize> = Vec::new();
+                for (state, row) in self.transitions.iter().enumerate()
+                {
+                    let signature = (group[state], row.iter().map(|(target, _)| group[*target]).collect());
+                    let size = signatures.len();
+                    next.push(*signatures.entry(signature).or_insert(size));
+                }
+                group = next;
+                if signatures.len() == count
+                {
+                    break;
+                }
+                count = signatures.len();
+            }
+
+            // Groups are numbered in the order of their first state,
+            // so state 0 stays the start.
+            let mut transitions: Vec<Vec<(usize, Vec<Phonet>)>> = vec![Vec::new(); count];
+            let mut final_output: Vec<Vec<Phonet>> = vec![Vec::new(); count];
+            for (state, row) in self.transitions.iter().enumerate()
+            {
+                if transitions[group[state]].is_empty()
+                {
+                    transitions[group[state]] = row.iter().map(|(target, output)| (group[*target], output.clone())).collect();
+                    final_output[group[state]] = self.final_output[state].clone();
+                }
+            }
+            Transducer {alphabet: self.alphabet.clone(), transitions, final_output, symbols: self.symbols.clone()}
+        }
+
+        // For each state, the phonets that can be written next from it,
+        // and whether the word can end before anything more is written.
+        fn next_written(&self) -> Vec<(HashSet<Phonet>, bool)>
+        {
+            let mut next: Vec<(HashSet<Phonet>, bool)> = self.final_output.iter()
+              .map(|output| (output.first().copied().into_iter().collect(), output.is_empty())).collect();
+            let mut changed = true;
+            while changed
+            {
+                changed = false;
+                for (state, row) in self.transitions.iter().enumerate()
+                {
+                    for (target, output) in row.iter()
+                    {
+                        let (phonets, can_end) = match output.first()
+                        {
+                            Some(phonet) => (vec![*phonet], false),
+                            None         => (next[*target].0.iter().copied().collect(), next[*target].1),
+                        };
+                        for phonet in phonets
+                        {
+                            changed |= next[state].0.insert(phonet);
+                        }
+                        if can_end && !next[state].1
+                        {
+                            next[state].1 = true;
+                            changed = true;
+                        }
+                    }
+                }
+            }
+            next
+        }
+
+        /// The transducer in the DOT language of Graphviz. Each arc is
+        /// labelled input:output, with ε for no output, and the final
+        /// output of a state is written under its name.
+        pub fn to_dot(&self) -> String
+        {
+            let ipa = |phonets: &[Phonet]| if phonets.is_empty() { String::from("ε") } else { phonets.iter().map(|x| construct_transcription(*x)).collect() };
+            let mut lines: Vec<String> = vec![String::from("digraph transducer {"), String::from("  rankdir=LR;")];
+            for (state, output) in self.final_output.iter().enumerate()
+            {
+                lines.push(format!("  q{} [shape=doublecircle, label=\"q{}\\n{}\"];", state, state, ipa(output)));
+            }
+            for (state, row) in self.transitions.iter().enumerate()
+            {
+                for (symbol, (next, output)) in row.iter().enumerate()
+                {
+                    lines.push(format!("  q{} -> q{} [label=\"{}:{}\"];", state, next, construct_transcription(self.alphabet[symbol]), ipa(output)));
+                }
+            }
+            lines.push(String::from("}"));
+            lines.join("\n")
+        }
+    }
+
+    // The state a window goes to, and what it writes.
+    type WindowStep = (usize, Vec<Phonet>);
+
+    // The states of a rule's window, numbered as they are reached, over
+    // phonets numbered by their position among the symbols. Each step
+    // from one is remembered, as composing takes the same steps from
+    // many states of the machine.
+    struct WindowStates<'w, 'a>
+    {
+        window: &'w Window<'a>,
+        symbols: &'w [Phonet],
+        states: Vec<WindowState>,
+        index: HashMap<WindowState, usize>,
+
+        // For each state and symbol: reading it, reading it ahead,
+        // and putting it in place of the phonet read ahead.
+        steps: Vec<Vec<Option<WindowStep>>>,
+        aheads: Vec<Vec<Option<WindowStep>>>,
+        fills: Vec<Vec<Option<WindowStep>>>
+    }
+
+    impl<'w, 'a> WindowStates<'w, 'a>
+    {
+        fn new(window: &'w Window<'a>, symbols: &'w [Phonet]) -> WindowStates<'w, 'a>
+        {
+            let mut states = WindowStates {window, symbols, states: Vec::new(), index: HashMap::new(),
+                                           steps: Vec::new(), aheads: Vec::new(), fills: Vec::new()};
+            states.number(window.initial());
+            states
+        }
+
+        fn number(&mut self, state: WindowState) -> usize
+        {
+            if let Some(number) = self.index.get(&state)
+            {
+                return *number;
+            }
+            self.index.insert(state.clone(), self.states.len());
+            self.states.push(state);
+            for table in [&mut self.steps, &mut self.aheads, &mut self.fills]
+            {
+                table.push(vec![None; self.symbols.len()]);
+            }
+            self.states.len() - 1
+        }
+
+        fn step(&mut self, state: usize, symbol: usize) -> (usize, &[Phonet])
+        {
+            if self.steps[state][symbol].is_none()
+            {
+                let (after, output) = self.window.step(&self.states[state], Some(self.symbols[symbol]));
+                self.steps[state][symbol] = Some((self.number(after), output));
+            }
+            let (after, output) = self.steps[state][symbol].as_ref().unwrap();
+            (*after, output)
+        }
+
+        // Reads a phonet that the machine has not written yet, and
+        // holds it back as the last held phonet.
+        fn read_ahead(&mut self, state: usize, symbol: usize) -> (usize, &[Phonet])
+        {
+            if self.aheads[state][symbol].is_none()
+            {
+                let (after, output) = self.window.advance(&self.states[state], Some(self.symbols[symbol]), 1);
+                self.aheads[state][symbol] = Some((self.number(after), output));
+            }
+            let (after, output) = self.aheads[state][symbol].as_ref().unwrap();
+            (*after, output)
+        }
+
+        // Puts the phonet in place of the last held one, which was read ahead.
+        fn fill(&mut self, state: usize, symbol: usize) -> (usize, &[Phonet])
+        {
+            if self.fills[state][symbol].is_none()
+            {
+                let mut filled = self.states[state].clone();
+                *filled.held.last_mut().unwrap() = self.symbols[symbol];
+                let mut output: Vec<Phonet> = Vec::new();
+                self.window.flush(&mut filled.held, 0, &mut output);
+                self.fills[state][symbol] = Some((self.number(filled), output));
+            }
+            let (filled, output) = self.fills[state][symbol].as_ref().unwrap();
+            (*filled, output)
+        }
+    }
+
+    // A state of a machine followed by a rule: the state of the machine,
+    // the number of the state of the rule's window after what the machine
+    // wrote, and whether the window has already read the class of the next
+    // phonet the machine will write, holding it as the last held phonet.
+    #[derive(Eq, PartialEq, Hash, Debug, Clone, Copy)]
+    struct CascadeState
+    {
+        machine: usize,
+        window: usize,
+        ahead: bool
+    }
+
+    // One transducer that does what a machine and then a rule do. This is
+    // what composing the machine with the rule's own machine gives, but
+    // where every phonet the machine can write next behaves the same for
+    // the rule, and the rule cannot change it, the window reads it ahead.
+    // Otherwise the window would hold back the phonets before it for as
+    // long as the machine holds back its own, one state for every pair.
+    fn compose_rule(machine: &Transducer, rule: &Rule) -> Result<Transducer, CompileError>
+    {
+        let mut symbols: Vec<Phonet> = machine.alphabet.clone();
+        for phonet in machine.output_alphabet()
+        {
+            if !symbols.contains(&phonet)
+            {
+                symbols.push(phonet);
+            }
+        }
+        let numbers: HashMap<Phonet, usize> = symbols.iter().enumerate().map(|(i, x)| (*x, i)).collect();
+        let number = |phonets: &[Phonet]| -> Vec<usize> { phonets.iter().map(|x| numbers[x]).collect() };
+        let written: Vec<Vec<Vec<usize>>> = machine.transitions.iter().map(|row| row.iter().map(|(_, output)| number(output)).collect()).collect();
+        let final_written: Vec<Vec<usize>> = machine.final_output.iter().map(|output| number(output)).collect();
+
+        let window = Window::new(rule, &symbols)?;
+        let states = RefCell::new(WindowStates::new(&window, &symbols));
+
+        // The class the window can read ahead from each state of the machine.
+        let can_read_ahead = window.inserted().is_none() && window.right > 0;
+        let ahead: Vec<Option<usize>> = machine.next_written().into_iter().map(|(phonets, can_end)|
+        {
+            let mut classes = phonets.iter().map(|x| window.representative(*x));
+            let class = classes.next().filter(|_| can_read_ahead && !can_end)?;
+            if classes.all(|x| x == class) && !window.is_target(class) { Some(numbers[&class]) } else { None }
+        }).collect();
+
+        // Reads what the machine wrote into the window, the first
+        // phonet in place of the one read ahead, if there is one.
+        let read = |state: CascadeState, written: &[usize], output: &mut Vec<Phonet>| -> CascadeState
+        {
+            let mut states = states.borrow_mut();
+            let mut current = state.window;
+            let mut written = written;
+            let mut ahead = state.ahead;
+            if ahead && !written.is_empty()
+            {
+                let (filled, step_output) = states.fill(current, written[0]);
+                output.extend_from_slice(step_output);
+                current = filled;
+                written = &written[1..];
+                ahead = false;
+            }
+            for symbol in written.iter()
+            {
+                let (next, step_output) = states.step(current, *symbol);
+                output.extend_from_slice(step_output);
+                current = next;
+            }
+            CascadeState {machine: state.machine, window: current, ahead}
+        };
+
+        let composed = explore(&machine.alphabet,
+                               CascadeState {machine: 0, window: 0, ahead: false},
+                               |state, symbol|
+                               {
+                                   let after = machine.transitions[state.machine][symbol].0;
+                                   let mut output: Vec<Phonet> = Vec::new();
+                                   let mut next = read(*state, &written[state.machine][symbol], &mut output);
+                                   next.machine = after;
+                                   if let (false, Some(class)) = (next.ahead, ahead[after])
+                                   {
+                                       let mut states = states.borrow_mut();
+                                       let (current, step_output) = states.read_ahead(next.window, class);
+                                       output.extend_from_slice(step_output);
+                                       next.window = current;
+                                       next.ahead = true;
+                                   }
+                                   Ok((next, output))
+                               },
+                               |state|
+                               {
+                                   let mut output: Vec<Phonet> = Vec::new();
+                                   let last = read(*state, &final_written[state.machine], &mut output);
+                                   output.extend(window.finish(&states.borrow().states[last.window]));
+                                   Ok(output)
+                               })?;
+        Ok(composed.minimized())
+    }
+
+    /// Compiles a cascade of rules into one transducer that reads words
+    /// made of the alphabet. The rules are added one at a time, each
+    /// reading what the rules before it can write, and the machine is
+    /// minimized after each.
+    pub fn compile_cascade(rules: &[Rule], alphabet: &[Phonet]) -> Result<Transducer, CompileError>
+    {
+        let mut cascade: Option<Transducer> = None;
+        for rule in rules.iter()
+        {
+            cascade = Some(match cascade
+            {
+                Some(before) => compose_rule(&before, rule)?,
+                None         => compile_rule(rule, alphabet)?.minimized(),
+            });
+        }
+        match cascade
+        {
+            Some(machine) => Ok(machine),
+            None => explore(alphabet, 0, |_, symbol| Ok((0, vec![alphabet[symbol]])), |_| Ok(Vec::new())),
+        }
+    }
+
+
+    #[cfg(test)]
+    mod transducer_tests
+    {
+        use super::*;
+        use crate::lib::{generate_from_unmarked, impossible, VocalFolds::*, Place::*, Manner::UnmarkedManner, Airstream::*,
+                         Height::*, Backness::UnmarkedBackness, Rounding::*};
+        use crate::rule::rewrite::rewrite::{parse_rule, apply_rule};
+        use crate::grapheme::international_phonetic_alphabet::international_phonetic_alphabet::can_transcribe;
+        use crate::grapheme::transcription::transcription::{parse_transcription, construct_segments_transcription};
+
+        fn phonets(text: &str) -> Vec<Phonet>
+        {
+            parse_transcription(text).iter().map(|x| x.phonet).collect()
+        }
+
+        fn ipa(phonets: &[Phonet]) -> String
+        {
+            phonets.iter().map(|x| construct_transcription(*x)).collect()
+        }
+
+        #[test]
+        fn compile_rule_test_same_as_apply_rule()
+        {
+            let alphabet = phonets("aptkbdg");
+            let words = ["apa", "akt", "tap", "bad", "a", "", "kakak"];
+            for text in ["p t k -> f θ x / V _ V", "[-son] -> [-voice] / _ #", "a -> 0 / # _", "0 -> ə / C _ C", "t -> d / (a) a _", "0 -> ə / _ #", "0 -> ə / # _"]
+            {
+                let rule = parse_rule(text).unwrap();
+                let machine = compile_rule(&rule, &alphabet).unwrap();
+                for word in words.iter()
+                {
+                    let expected = construct_segments_transcription(&apply_rule(&rule, &parse_transcription(word)));
+                    assert_eq!(ipa(&machine.apply(&phonets(word)).unwrap()), expected, "{} on {}", text, word);
+                }
+            }
+        }
+
+        // Every phonet with an IPA symbol that can be articulated.
+        fn ipa_alphabet() -> Vec<Phonet>
+        {
+            let consonant = Phonet::Consonant {vocal_folds: UnmarkedVocalFolds, place: UnmarkedPlace, manner: UnmarkedManner, airstream: PulmonicEgressive};
+            let vowel = Phonet::Vowel {height: UnmarkedHeight, backness: UnmarkedBackness, rounding: UnmarkedRounding, vocal_folds: Voiced};
+            let mut alphabet = generate_from_unmarked(consonant);
+            alphabet.extend(generate_from_unmarked(vowel));
+            alphabet.into_iter().filter(|x| !impossible(*x) && can_transcribe(*x)).collect()
+        }
+
+        #[test]
+        fn compile_rule_test_ipa_alphabet()
+        {
+            let alphabet = ipa_alphabet();
+            assert!(alphabet.len() > 80);
+            let words = ["apa", "ata", "akat", "pato", "abaka", "mipuk"];
+            for text in ["p t k -> f θ x / V _ V", "a -> e / _ C V", "[-son] -> [-voice] / _ #"]
+            {
+                let rule = parse_rule(text).unwrap();
+                let machine = compile_rule(&rule, &alphabet).unwrap();
+                assert!(machine.state_count() < 1000, "{} has {} states", text, machine.state_count());
+                for word in words.iter()
+                {
+                    let expected = construct_segments_transcription(&apply_rule(&rule, &parse_transcription(word)));
+                    assert_eq!(ipa(&machine.apply(&phonets(word)).unwrap()), expected, "{} on {}", text, word);
+                }
+            }
+        }
+
+        #[test]
+        fn compile_cascade_test()
+        {
+            let rules = vec![parse_rule("e -> 0 / _ #").unwrap(), parse_rule("[-son] -> [-voice] / _ #").unwrap()];
+            let machine = compile_cascade(&rules, &phonets("bade")).unwrap();
+            let lexicon: Vec<Vec<Phonet>> = ["bade", "bad", "ebe"].iter().map(|x| phonets(x)).collect();
+            let results: Vec<String> = machine.apply_to_lexicon(&lexicon).iter().map(|x| ipa(x.as_ref().unwrap())).collect();
+            assert_eq!(results, ["bat", "bat", "ep"]);
+            assert_eq!(machine.apply(&phonets("ax")), None);
+            assert_eq!(compile_rule(&parse_rule("a -> e / _ C*").unwrap(), &phonets("a")), Err(CompileError::UnboundedEnvironment));
+        }
+
+        #[test]
+        fn compile_cascade_test_ipa_alphabet()
+        {
+            let texts = ["e -> 0 / _ #", "[-son] -> [-voice] / _ #", "p t k -> f θ x / V _ V", "n -> m / _ p", "a -> e / _ C i"];
+            let rules: Vec<Rule> = texts.iter().map(|x| parse_rule(x).unwrap()).collect();
+            let machine = compile_cascade(&rules, &ipa_alphabet()).unwrap();
+            assert!(machine.state_count() < 1000, "the cascade has {} states", machine.state_count());
+            for word in ["apa", "anpe", "abadi", "tabe", "katig", "ʔaŋkʰe", "", "e", "obed", "ampaki"].iter()
+            {
+                let mut expected = parse_transcription(word);
+                for rule in rules.iter()
+                {
+                    expected = apply_rule(rule, &expected);
+                }
+                assert_eq!(ipa(&machine.apply(&phonets(word)).unwrap()), construct_segments_transcription(&expected), "on {}", word);
+            }
+        }
+
+        #[test]
+        fn compose_test()
+        {
+            let alphabet = phonets("bade");
+            let first = compile_rule(&parse_rule("e -> 0 / _ #").unwrap(), &alphabet).unwrap();
+            let second = compile_rule(&parse_rule("[-son] -> [-voice] / _ #").unwrap(), &alphabet).unwrap();
+            let machine = first.compose(&second).unwrap();
+            assert_eq!(machine.state_count(), machine.minimized().state_count());
+            for (word, expected) in [("bade", "bat"), ("bad", "bat"), ("ebe", "ep"), ("", "")]
+            {
+                assert_eq!(ipa(&machine.apply(&phonets(word)).unwrap()), expected, "on {}", word);
+            }
+        }
+
+        #[test]
+        fn to_dot_test()
+        {
+            let machine = compile_rule(&parse_rule("a -> e").unwrap(), &phonets("a")).unwrap();
+            let dot = machine.to_dot();
+            assert!(dot.starts_with("digraph transducer {"));
+            assert!(dot.contains("q0 -> q1 [label=\"a:e\"];"));
+        }
+    }
+}
@@ -0,0 +1,189 @@
+pub mod segment
+{
+    use crate::lib::{Phonet, Phonet::*, aspirated_phonet, deaspirated_phonet};
+    use SecondaryArticulation::*;
+    use Length::*;
+    use Stress::*;
+
+    /// An articulation made at the same time as the main one,
+    /// but with less constriction. In IPA it is written
+    /// with a superscript after the main symbol.
+    #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+    pub enum SecondaryArticulation
+    {
+        Plain,          // No secondary articulation.
+        Labialized,     // ʷ
+        Palatalized,    // ʲ
+        Velarized,      // ˠ
+        Pharyngealized  // ˤ
+    }
+
+    pub static SECONDARY_ARTICULATION_STATES: [SecondaryArticulation; 5] =
+      [Plain, Labialized, Palatalized, Velarized, Pharyngealized];
+
+
+    /// How long a segment is held. A long consonant is a geminate.
+    #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+    pub enum Length
+    {
+        Short,     // No length mark.
+        HalfLong,  // ˑ
+        Long       // ː
+    }
+
+    pub static LENGTH_STATES: [Length; 3] = [Short, HalfLong, Long];
+
+
+    /// The stress of a syllable.
+    #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+    pub enum Stress
+    {
+        Unstressed,
+        Primary,    // ˈ
+        Secondary   // ˌ
+    }
+
+    pub static STRESS_STATES: [Stress; 3] = [Unstressed, Primary, Secondary];
+
+
+    /// A phonet together with the properties that
+    /// are not part of its place, manner, voicing or vowel quality.
+    #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+    pub struct Segment
+    {
+        pub phonet: Phonet,
+        pub secondary: SecondaryArticulation,
+        pub length: Length,
+
+        // A puff of breath before the segment, written ʰ before it.
+        pub preaspirated: bool,
+
+        // The stress mark written before the segment, if any.
+        // It is on the first segment of the stressed syllable,
+        // which is not always the vowel (see syllable_stress).
+        pub stress: Stress
+    }
+
+    impl Segment
+    {
+        /// A segment with nothing added to the phonet.
+        pub fn new(phonet: Phonet) -> Segment
+        {
+            Segment {phonet, secondary: Plain, length: Short, preaspirated: false, stress: Unstressed}
+        }
+    }
+
+    impl From<Phonet> for Segment
+    {
+        fn from(phonet: Phonet) -> Segment
+        {
+            Segment::new(phonet)
+        }
+    }
+
+
+    /// The stress of the vowel at an index: the nearest stress mark
+    /// on it or before it, as long as there is no other syllable in between.
+    /// Vowels next to each other count as one syllable (a diphthong).
+    /// For a consonant this is the stress of the syllable it is
+    /// the onset of. Returns Unstressed if the index is out of range.
+    pub fn syllable_stress(segments: &[Segment], index: usize) -> Stress
+    {
+        if index >= segments.len()
+        {
+            return Unstressed;
+        }
+        for i in (0..=index).rev()
+        {
+            if segments[i].stress != Unstressed
+            {
+                return segments[i].stress;
+            }
+            let is_vowel = |segment: Segment| matches!(segment.phonet, Vowel {..});
+            if i < index && is_vowel(segments[i]) && !is_vowel(segments[i + 1])
+            {
+                break;
+            }
+        }
+        Unstressed
+    }
+
+    /// Aspirates the phonet of a segment (see aspirated_phonet).
+    /// A preaspirated segment loses its preaspiration,
+    /// so that it is not aspirated on both sides.
+    pub fn aspirated_segment(segment: Segment) -> Segment
+    {
+        match segment.phonet
+        {
+            Consonant {..} => Segment {phonet: aspirated_phonet(segment.phonet), preaspirated: false, ..segment},
+            Vowel {..}     => segment,
+        }
+    }
+
+    /// Removes both aspiration and preaspiration from a segment.
+    pub fn deaspirated_segment(segment: Segment) -> Segment
+    {
+        Segment {phonet: deaspirated_phonet(segment.phonet), preaspirated: false, ..segment}
+    }
+
+    /// Preaspirates a consonant: t becomes ʰt, and tʰ becomes ʰt
+    /// (the aspiration moves before it). Vowels stay as they are.
+    pub fn preaspirated_segment(segment: Segment) -> Segment
+    {
+        match segment.phonet
+        {
+            Consonant {..} => Segment {phonet: deaspirated_phonet(segment.phonet), preaspirated: true, ..segment},
+            Vowel {..}     => segment,
+        }
+    }
+
+    /// Makes a segment long. For a consonant this is a geminate
+    /// (t becomes tː), for a vowel it is a long vowel.
+    pub fn geminated_segment(segment: Segment) -> Segment
+    {
+        Segment {length: Long, ..segment}
+    }
+
+    /// Makes a long or half-long segment short.
+    pub fn degeminated_segment(segment: Segment) -> Segment
+    {
+        Segment {length: Short, ..segment}
+    }
+
+
+    #[cfg(test)]
+    mod segment_tests
+    {
+        use super::*;
+        use crate::grapheme::transcription::transcription::{parse_transcription, construct_segments_transcription};
+
+        fn change_text(text: &str, change: fn(Segment) -> Segment) -> String
+        {
+            let changed: Vec<Segment> = parse_transcription(text).into_iter().map(change).collect();
+            construct_segments_transcription(&changed)
+        }
+
+        #[test]
+        fn aspirated_segment_test()
+        {
+            assert_eq!(change_text("pbaʰt", aspirated_segment), "pʰbʰatʰ");
+            assert_eq!(change_text("pʰbʰatʰ", deaspirated_segment), "pbat");
+            assert_eq!(change_text("tʰa", preaspirated_segment), "ʰta");
+        }
+
+        #[test]
+        fn syllable_stress_test()
+        {
+            let segments = parse_transcription("ɪnˈsaɪdə");
+            let stresses: Vec<Stress> = (0..segments.len()).map(|i| syllable_stress(&segments, i)).collect();
+            assert_eq!(stresses, [Unstressed, Unstressed, Primary, Primary, Primary, Unstressed, Unstressed]);
+        }
+
+        #[test]
+        fn geminated_segment_test()
+        {
+            assert_eq!(change_text("tak", geminated_segment), "tːaːkː");
+            assert_eq!(change_text("tːaˑ", degeminated_segment), "ta");
+        }
+    }
+}
@@ -0,0 +1,31 @@
+pub mod table
+{
+    /// Lays out rows of cells as a plain text table,
+    /// with the columns separated by " | ".
+    pub fn text_table(rows: &[Vec<String>]) -> String
+    {
+        let columns = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+        let widths: Vec<usize> = (0..columns).map(|i| rows.iter().filter_map(|row| row.get(i)).map(|x| x.chars().count()).max().unwrap_or(0)).collect();
+        let lines: Vec<String> = rows.iter().map(|row|
+        {
+            let cells: Vec<String> = row.iter().enumerate().map(|(i, cell)| format!("{}{}", cell, " ".repeat(widths[i] - cell.chars().count()))).collect();
+            String::from(cells.join(" | ").trim_end())
+        }).collect();
+        lines.join("\n")
+    }
+
+
+    #[cfg(test)]
+    mod table_tests
+    {
+        use super::*;
+
+        #[test]
+        fn text_table_test()
+        {
+            let rows = vec![vec![String::from("UR"), String::from("/bad/")], vec![String::from("rule 1"), String::from("bat")]];
+            assert_eq!(text_table(&rows), "UR     | /bad/\nrule 1 | bat");
+            assert_eq!(text_table(&[]), "");
+        }
+    }
+}
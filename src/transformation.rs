@@ -0,0 +1,375 @@
+pub mod transformation
+{
+    use std::fmt;
+    use crate::lib::{Phonet, voiced_phonet, devoiced_phonet, spirantized_phonet, aspirated_phonet, deaspirated_phonet,
+                     retracted_place, advanced_place, Place, Phonet::*};
+    use crate::segment::segment::Segment;
+
+    /// Something that changes one phonet into another,
+    /// such as voicing or spirantization.
+    pub trait PhonetTransformation
+    {
+        /// Transforms one phonet.
+        fn apply(&self, phonet: Phonet) -> Phonet;
+
+        /// A short name, used to describe a pipeline
+        /// and to look the transformation up by name.
+        fn name(&self) -> String;
+
+        /// The transformation that undoes this one, if there is one:
+        /// given any phonet that this one changed, it gives back the
+        /// phonet it started from. Voicing has none, as it turns a
+        /// creaky voiced b into b, which devoicing makes a p.
+        fn inverse(&self) -> Option<Box<dyn PhonetTransformation>>
+        {
+            None
+        }
+
+        /// Transforms every phonet of a sequence.
+        fn apply_to_all(&self, phonets: &[Phonet]) -> Vec<Phonet>
+        {
+            phonets.iter().map(|phonet| self.apply(*phonet)).collect()
+        }
+
+        /// Transforms the phonet of every segment of a sequence,
+        /// keeping the rest of each segment as it is.
+        fn apply_to_segments(&self, segments: &[Segment]) -> Vec<Segment>
+        {
+            segments.iter().map(|segment| Segment {phonet: self.apply(segment.phonet), ..*segment}).collect()
+        }
+    }
+
+
+    /// Makes a phonet voiced (see voiced_phonet).
+    #[derive(Debug, Clone, Copy)]
+    pub struct Voice;
+
+    /// Makes a phonet voiceless (see devoiced_phonet).
+    #[derive(Debug, Clone, Copy)]
+    pub struct Devoice;
+
+    /// Turns plosives into fricatives (see spirantized_phonet).
+    #[derive(Debug, Clone, Copy)]
+    pub struct Spirantize;
+
+    /// Aspirates a consonant (see aspirated_phonet).
+    #[derive(Debug, Clone, Copy)]
+    pub struct Aspirate;
+
+    /// Removes the aspiration of a consonant (see deaspirated_phonet).
+    #[derive(Debug, Clone, Copy)]
+    pub struct Deaspirate;
+
+    /// Moves the place of articulation of a consonant
+    /// one step back (see retracted_place).
+    #[derive(Debug, Clone, Copy)]
+    pub struct Retract;
+
+    /// Moves the place of articulation of a consonant
+    /// one step forward (see advanced_place).
+    #[derive(Debug, Clone, Copy)]
+    pub struct Advance;
+
+    impl PhonetTransformation for Voice
+    {
+        fn apply(&self, phonet: Phonet) -> Phonet
+        {
+            voiced_phonet(phonet)
+        }
+
+        fn name(&self) -> String
+        {
+            String::from("voice")
+        }
+    }
+
+    impl PhonetTransformation for Devoice
+    {
+        fn apply(&self, phonet: Phonet) -> Phonet
+        {
+            devoiced_phonet(phonet)
+        }
+
+        fn name(&self) -> String
+        {
+            String::from("devoice")
+        }
+    }
+
+    impl PhonetTransformation for Spirantize
+    {
+        fn apply(&self, phonet: Phonet) -> Phonet
+        {
+            spirantized_phonet(phonet)
+        }
+
+        fn name(&self) -> String
+        {
+            String::from("spirantize")
+        }
+    }
+
+    impl PhonetTransformation for Aspirate
+    {
+        fn apply(&self, phonet: Phonet) -> Phonet
+        {
+            aspirated_phonet(phonet)
+        }
+
+        fn name(&self) -> String
+        {
+            String::from("aspirate")
+        }
+
+        fn inverse(&self) -> Option<Box<dyn PhonetTransformation>>
+        {
+            Some(Box::new(Deaspirate))
+        }
+    }
+
+    impl PhonetTransformation for Deaspirate
+    {
+        fn apply(&self, phonet: Phonet) -> Phonet
+        {
+            deaspirated_phonet(phonet)
+        }
+
+        fn name(&self) -> String
+        {
+            String::from("deaspirate")
+        }
+
+        fn inverse(&self) -> Option<Box<dyn PhonetTransformation>>
+        {
+            Some(Box::new(Aspirate))
+        }
+    }
+
+    fn with_place(phonet: Phonet, shift: fn(Place) -> Place) -> Phonet
+    {
+        match phonet
+        {
+            Consonant {vocal_folds, place, manner, airstream} =>
+              Consonant {vocal_folds, place: shift(place), manner, airstream},
+            vowel => vowel,
+        }
+    }
+
+    impl PhonetTransformation for Retract
+    {
+        fn apply(&self, phonet: Phonet) -> Phonet
+        {
+            with_place(phonet, retracted_place)
+        }
+
+        fn name(&self) -> String
+        {
+            String::from("retract")
+        }
+    }
+
+    impl PhonetTransformation for Advance
+    {
+        fn apply(&self, phonet: Phonet) -> Phonet
+        {
+            with_place(phonet, advanced_place)
+        }
+
+        fn name(&self) -> String
+        {
+            String::from("advance")
+        }
+    }
+
+
+    /// Several transformations applied one after the other.
+    /// A pipeline is itself a transformation, so pipelines can
+    /// be nested, and it can be given a name of its own.
+    pub struct Pipeline
+    {
+        name: Option<String>,
+        steps: Vec<Box<dyn PhonetTransformation>>
+    }
+
+    impl Pipeline
+    {
+        /// A pipeline with no steps, which leaves every phonet as it is.
+        pub fn new() -> Pipeline
+        {
+            Pipeline {name: None, steps: Vec::new()}
+        }
+
+        /// Adds a step to the end of the pipeline.
+        pub fn then<T: PhonetTransformation + 'static>(self, step: T) -> Pipeline
+        {
+            self.then_boxed(Box::new(step))
+        }
+
+        /// Adds a step that is already boxed to the end of the pipeline.
+        pub fn then_boxed(mut self, step: Box<dyn PhonetTransformation>) -> Pipeline
+        {
+            self.steps.push(step);
+            self
+        }
+
+        /// Gives the pipeline a name, to use instead of listing its steps.
+        pub fn named(self, name: &str) -> Pipeline
+        {
+            Pipeline {name: Some(String::from(name)), ..self}
+        }
+    }
+
+    impl Default for Pipeline
+    {
+        fn default() -> Pipeline
+        {
+            Pipeline::new()
+        }
+    }
+
+    impl PhonetTransformation for Pipeline
+    {
+        fn apply(&self, phonet: Phonet) -> Phonet
+        {
+            self.steps.iter().fold(phonet, |acc, step| step.apply(acc))
+        }
+
+        fn name(&self) -> String
+        {
+            match &self.name
+            {
+                Some(name) => name.clone(),
+                None       => self.steps.iter().map(|step| step.name()).collect::<Vec<String>>().join(" then "),
+            }
+        }
+
+        // Only if every step can be undone,
+        // then the inverses are applied in the opposite order.
+        // This gives back a phonet that every step changed, but
+        // not always one that some step left as it was.
+        fn inverse(&self) -> Option<Box<dyn PhonetTransformation>>
+        {
+            let mut inverse = Pipeline::new();
+            for step in self.steps.iter().rev()
+            {
+                inverse = inverse.then_boxed(step.inverse()?);
+            }
+            if let Some(name) = &self.name
+            {
+                inverse = inverse.named(&format!("undo {}", name));
+            }
+            Some(Box::new(inverse))
+        }
+    }
+
+
+    /// Looks up one of the transformations by its name.
+    pub fn transformation_named(name: &str) -> Option<Box<dyn PhonetTransformation>>
+    {
+        match name
+        {
+            "voice"      => Some(Box::new(Voice)),
+            "devoice"    => Some(Box::new(Devoice)),
+            "spirantize" => Some(Box::new(Spirantize)),
+            "retract"    => Some(Box::new(Retract)),
+            "advance"    => Some(Box::new(Advance)),
+            "aspirate"   => Some(Box::new(Aspirate)),
+            "deaspirate" => Some(Box::new(Deaspirate)),
+            _            => None,
+        }
+    }
+
+    /// A name that parse_pipeline did not recognize.
+    #[derive(Eq, PartialEq, Debug, Clone)]
+    pub struct UnknownTransformation(pub String);
+
+    impl fmt::Display for UnknownTransformation
+    {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+        {
+            write!(f, "unknown transformation: {}", self.0)
+        }
+    }
+
+    /// Builds a pipeline from text like "devoice then spirantize".
+    /// Steps can also be separated by commas.
+    pub fn parse_pipeline(text: &str) -> Result<Pipeline, UnknownTransformation>
+    {
+        let mut pipeline = Pipeline::new();
+        for word in text.replace(',', " then ").split_whitespace().filter(|word| *word != "then")
+        {
+            let step = transformation_named(word).ok_or_else(|| UnknownTransformation(String::from(word)))?;
+            pipeline = pipeline.then_boxed(step);
+        }
+        Ok(pipeline)
+    }
+
+
+    #[cfg(test)]
+    mod transformation_tests
+    {
+        use super::*;
+        use crate::lib::{generate_from_unmarked, VocalFolds::*, Place::*, Manner::*, Airstream::*, Height::*, Backness::*, Rounding::*};
+        use crate::grapheme::transcription::transcription::{parse_transcription, construct_segments_transcription};
+
+        #[test]
+        fn parse_pipeline_test_devoice_then_spirantize()
+        {
+            let pipeline = parse_pipeline("devoice then spirantize").unwrap();
+            assert_eq!(pipeline.name(), "devoice then spirantize");
+            let result = pipeline.apply_to_segments(&parse_transcription("bdg"));
+            assert_eq!(construct_segments_transcription(&result), "ɸθx");
+        }
+
+        #[test]
+        fn pipeline_inverse_test()
+        {
+            let pipeline = Pipeline::new().named("nothing");
+            assert_eq!(pipeline.inverse().unwrap().name(), "undo nothing");
+            assert!(Pipeline::new().then(Spirantize).inverse().is_none());
+            assert!(Pipeline::new().then(Voice).then(Retract).inverse().is_none());
+        }
+
+        #[test]
+        fn inverse_test_voicing()
+        {
+            // Devoicing after voicing does not give back a creaky b.
+            let creaky_b = Consonant {vocal_folds: CreakyVoiced, place: Bilabial, manner: Plosive, airstream: PulmonicEgressive};
+            assert_ne!(Devoice.apply(Voice.apply(creaky_b)), creaky_b);
+            assert!(Voice.inverse().is_none());
+            assert!(Devoice.inverse().is_none());
+        }
+
+        #[test]
+        fn inverse_test_place()
+        {
+            // Retracting after advancing gives back PostAlveolar
+            // for a PalatoAlveolar.
+            let palato_alveolar = Consonant {vocal_folds: Voiceless, place: PalatoAlveolar, manner: Fricative, airstream: PulmonicEgressive};
+            assert_ne!(Retract.apply(Advance.apply(palato_alveolar)), palato_alveolar);
+            assert!(Retract.inverse().is_none());
+            assert!(Advance.inverse().is_none());
+        }
+
+        #[test]
+        fn inverse_test_aspiration()
+        {
+            let consonant = Consonant {vocal_folds: UnmarkedVocalFolds, place: UnmarkedPlace, manner: UnmarkedManner, airstream: UnmarkedAirstream};
+            let vowel = Vowel {height: UnmarkedHeight, backness: UnmarkedBackness, rounding: UnmarkedRounding, vocal_folds: UnmarkedVocalFolds};
+            let mut phonets = generate_from_unmarked(consonant);
+            phonets.extend(generate_from_unmarked(vowel));
+            let transformations: [Box<dyn PhonetTransformation>; 2] = [Box::new(Aspirate), Box::new(Deaspirate)];
+            for transformation in transformations.iter()
+            {
+                let inverse = transformation.inverse().unwrap();
+                for phonet in phonets.iter().copied().filter(|x| transformation.apply(*x) != *x)
+                {
+                    assert_eq!(inverse.apply(transformation.apply(phonet)), phonet);
+                }
+            }
+            let pipeline = Pipeline::new().then(Aspirate).named("aspiration");
+            let p = parse_transcription("p")[0].phonet;
+            assert_eq!(pipeline.inverse().unwrap().apply(pipeline.apply(p)), p);
+        }
+    }
+}